@@ -1,24 +1,149 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use tracing::warn;
 use tracing_subscriber::EnvFilter;
 
-use generate_sifis_hazards::{adds_hazards_to_api, Templates};
+use anyhow::bail;
+use generate_sifis_hazards::{
+    check_ontology, diff_ontologies, Format, Generator, StaleFiles, Templates,
+};
+use similar::TextDiff;
 
 #[derive(Parser, Debug)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Opts {
     /// Output the generated paths as they are produced
     #[clap(short, long, global = true)]
     verbose: bool,
+    #[clap(subcommand)]
+    command: Option<Command>,
     /// Name of a builtin template
-    #[clap(long, short, possible_values = Templates::variants())]
-    template: Templates,
+    #[clap(
+        long,
+        short,
+        possible_values = Templates::variants(),
+        required_unless_present = "template-dir"
+    )]
+    template: Option<Templates>,
+    /// Directory of user-supplied templates, with a `templates.toml` manifest
+    #[clap(long, parse(from_os_str), conflicts_with = "template")]
+    template_dir: Option<PathBuf>,
+    /// Format of the ontology, detected from its extension by default
+    #[clap(long, short, possible_values = Format::variants())]
+    format: Option<Format>,
     /// Path to the ontology file
-    #[clap(parse(from_os_str))]
-    ontology_path: PathBuf,
+    #[clap(parse(from_os_str), required = true)]
+    ontology_path: Option<PathBuf>,
+    /// Lockfile of the bits of the hazards, the ontology path with the
+    /// `lock` extension by default
+    #[clap(long, parse(from_os_str))]
+    lockfile: Option<PathBuf>,
     /// Path to the generated API
-    #[clap(parse(from_os_str))]
-    output_path: PathBuf,
+    #[clap(parse(from_os_str), required_unless_present = "stdout")]
+    output_path: Option<PathBuf>,
+    /// List the files which would be written, without writing them
+    #[clap(long, conflicts_with_all = &["stdout", "check"])]
+    dry_run: bool,
+    /// Print a generated file, given its relative path as `--stdout=PATH`,
+    /// instead of writing it. The path can be omitted when the template
+    /// generates a single file
+    #[clap(
+        long,
+        value_parser,
+        require_equals = true,
+        value_name = "PATH",
+        conflicts_with = "check"
+    )]
+    stdout: Option<Option<PathBuf>>,
+    /// Compare the generated files with the existing ones, printing their
+    /// differences and exiting with an error if they differ
+    #[clap(long)]
+    check: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check the consistency of the ontology
+    Check {
+        /// Builtin templates whose identifiers are checked, all by default
+        #[clap(long, short, possible_values = Templates::variants())]
+        template: Vec<Templates>,
+        /// Format of the ontology, detected from its extension by default
+        #[clap(long, short, possible_values = Format::variants())]
+        format: Option<Format>,
+        /// Path to the ontology file
+        #[clap(parse(from_os_str))]
+        ontology_path: PathBuf,
+    },
+    /// Compare two versions of the ontology
+    Diff {
+        /// Format of the ontologies, detected from their extensions by default
+        #[clap(long, short, possible_values = Format::variants())]
+        format: Option<Format>,
+        /// Path to the old ontology file
+        #[clap(parse(from_os_str))]
+        old_path: PathBuf,
+        /// Path to the new ontology file
+        #[clap(parse(from_os_str))]
+        new_path: PathBuf,
+    },
+}
+
+// Returns the generated file with the given path, or the only one of the
+// template when no path is given.
+fn generated_file(generator: &Generator, path: Option<PathBuf>) -> anyhow::Result<String> {
+    if let Some(path) = path {
+        return generator.render_file(&path);
+    }
+
+    let mut files = generator.render()?;
+    if files.len() != 1 {
+        let paths: Vec<_> = files
+            .keys()
+            .map(|path| path.display().to_string())
+            .collect();
+        bail!(
+            "The template generates more than one file, choose one of: {}",
+            paths.join(", ")
+        );
+    }
+
+    Ok(files.pop_first().unwrap().1)
+}
+
+// Prints the differences between the stale files and the generated ones.
+fn print_differences(
+    generator: &Generator,
+    output_path: &Path,
+    stale: &StaleFiles,
+) -> anyhow::Result<()> {
+    let mut files: BTreeMap<_, _> = generator
+        .render()?
+        .into_iter()
+        .map(|(path, content)| (output_path.join(path), content))
+        .collect();
+    files.insert(
+        generator.lockfile_path().to_path_buf(),
+        generator.render_lockfile(),
+    );
+
+    for path in &stale.files {
+        // A missing file is shown as an empty one
+        let existing = fs::read_to_string(path).unwrap_or_default();
+        let content = files.remove(path).unwrap_or_default();
+        let path = path.display().to_string();
+        print!(
+            "{}",
+            TextDiff::from_lines(&existing, &content)
+                .unified_diff()
+                .header(&path, &path)
+        );
+    }
+
+    Ok(())
 }
 
 lazy_static::lazy_static! {
@@ -44,7 +169,70 @@ fn main() -> anyhow::Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
-    adds_hazards_to_api(opts.template, &opts.ontology_path, &opts.output_path)?;
+    match opts.command {
+        Some(Command::Check {
+            template,
+            format,
+            ontology_path,
+        }) => {
+            let templates = if template.is_empty() {
+                Templates::all()
+            } else {
+                template
+            };
+            let report = check_ontology(&ontology_path, format, &templates)?;
+            print!("{}", report);
+            if report.has_errors() {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Diff {
+            format,
+            old_path,
+            new_path,
+        }) => print!("{}", diff_ontologies(&old_path, &new_path, format)?),
+        // Required arguments are always present without a subcommand
+        None => {
+            let ontology_path = opts.ontology_path.unwrap();
+            let generator = match opts.template_dir {
+                Some(template_dir) => {
+                    Generator::open_template_dir(&template_dir, &ontology_path, opts.format)?
+                }
+                None => Generator::open(opts.template.unwrap(), &ontology_path, opts.format)?,
+            };
+            let generator = match opts.lockfile {
+                Some(lockfile) => generator.with_lockfile(&lockfile)?,
+                None => generator,
+            };
+            if let Some(warning) = generator.rename_warning() {
+                warn!("{}", warning);
+            }
+
+            if let Some(path) = opts.stdout {
+                print!("{}", generated_file(&generator, path)?);
+                return Ok(());
+            }
+
+            let output_path = opts.output_path.unwrap();
+            if opts.dry_run {
+                for path in generator.render()?.keys() {
+                    println!("{}", output_path.join(path).display());
+                }
+                let lockfile = fs::read_to_string(generator.lockfile_path()).ok();
+                if lockfile != Some(generator.render_lockfile()) {
+                    println!("{}", generator.lockfile_path().display());
+                }
+            } else if opts.check {
+                if let Err(err) = generator.check(&output_path) {
+                    let stale = err.downcast::<StaleFiles>()?;
+                    print_differences(&generator, &output_path, &stale)?;
+                    std::process::exit(1);
+                }
+            } else {
+                generator.write(&output_path)?;
+            }
+        }
+    }
 
     Ok(())
 }
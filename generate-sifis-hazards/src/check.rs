@@ -0,0 +1,312 @@
+//! Consistency checks of the ontology.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::graph::{Graph, Node, Object};
+use crate::ontology::{local_name, risk_score_range, sho, RiskScoreRange};
+use crate::rdf::Format;
+use crate::Templates;
+
+/// Severity of a problem
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The code can be generated, but the ontology should be fixed
+    Warning,
+    /// The code cannot be generated or would not be valid
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Problem found in the ontology
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub severity: Severity,
+    /// Identifier of the offending node.
+    pub node: String,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: `{}`: {}", self.severity, self.node, self.message)
+    }
+}
+
+/// Outcome of the checks
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CheckReport {
+    pub problems: Vec<Problem>,
+}
+
+impl CheckReport {
+    /// Returns whether any problem is an error.
+    pub fn has_errors(&self) -> bool {
+        self.problems
+            .iter()
+            .any(|problem| problem.severity == Severity::Error)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.problems
+            .iter()
+            .filter(|problem| problem.severity == severity)
+            .count()
+    }
+
+    fn push(&mut self, severity: Severity, node: &str, message: String) {
+        self.problems.push(Problem {
+            severity,
+            node: node.to_owned(),
+            message,
+        });
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for problem in &self.problems {
+            writeln!(f, "{}", problem)?;
+        }
+        writeln!(
+            f,
+            "{} errors, {} warnings",
+            self.count(Severity::Error),
+            self.count(Severity::Warning)
+        )
+    }
+}
+
+// Checks the names and the descriptions of the nodes of a kind.
+fn check_names(report: &mut CheckReport, kind: &str, nodes: &[&Node], templates: &[Templates]) {
+    let mut names = HashMap::new();
+
+    for node in nodes {
+        if node.literals(&sho("description")).next().is_none() {
+            report.push(Severity::Error, &node.id, "missing description".to_owned());
+        }
+
+        let name = match local_name(&node.id) {
+            Ok(name) => name,
+            Err(e) => {
+                report.push(Severity::Error, &node.id, e.message);
+                continue;
+            }
+        };
+        // Names differing only by case collide in case-converting templates
+        if let Some(other) = names.insert(name.to_lowercase(), &node.id) {
+            report.push(
+                Severity::Error,
+                &node.id,
+                format!("{} name `{}` already used by `{}`", kind, name, other),
+            );
+        }
+        for template in templates {
+            if !template.is_valid_identifier(name) {
+                report.push(
+                    Severity::Error,
+                    &node.id,
+                    format!(
+                        "`{}` is not a valid identifier for the {} template",
+                        name, template
+                    ),
+                );
+            }
+        }
+    }
+}
+
+// Checks the category and the risk score of a hazard.
+fn check_hazard(report: &mut CheckReport, graph: &Graph, hazard: &Node, range: &RiskScoreRange) {
+    match hazard.values(&sho("hasCategory")) {
+        [] => report.push(
+            Severity::Error,
+            &hazard.id,
+            "missing `hasCategory`".to_owned(),
+        ),
+        [Object::Iri(category)] => {
+            if !graph
+                .node(category)
+                .is_some_and(|node| node.has_type(&sho("Category")))
+            {
+                report.push(
+                    Severity::Error,
+                    &hazard.id,
+                    format!("`hasCategory` references `{}`, not a category", category),
+                );
+            }
+        }
+        [_] => report.push(
+            Severity::Error,
+            &hazard.id,
+            "`hasCategory` must reference a category".to_owned(),
+        ),
+        _ => report.push(
+            Severity::Error,
+            &hazard.id,
+            "more than one `hasCategory`".to_owned(),
+        ),
+    }
+
+    for risk_score in hazard.values(&sho("riskScore")) {
+        // An empty risk score only declares that the hazard has one
+        let message = match risk_score.as_literal() {
+            Some(("", _)) => continue,
+            Some((value, _)) => match value.parse::<i64>() {
+                Ok(value) if (range.min..=range.max).contains(&value) => continue,
+                Ok(value) => format!(
+                    "risk score {} out of the range [{}, {}]",
+                    value, range.min, range.max
+                ),
+                Err(_) => format!("invalid risk score `{}`", value),
+            },
+            None => "risk score must be a literal".to_owned(),
+        };
+        report.push(Severity::Error, &hazard.id, message);
+    }
+}
+
+/// Checks the consistency of an ontology graph.
+pub(crate) fn check_graph(graph: &Graph, templates: &[Templates]) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    let range = risk_score_range(graph).unwrap_or_else(|| {
+        report.push(
+            Severity::Warning,
+            &sho("level"),
+            "risk score range not found, using the default one".to_owned(),
+        );
+        RiskScoreRange { min: 0, max: 10 }
+    });
+
+    let hazards: Vec<_> = graph
+        .nodes()
+        .filter(|node| node.has_type(&sho("Hazard")))
+        .collect();
+    let categories: Vec<_> = graph
+        .nodes()
+        .filter(|node| node.has_type(&sho("Category")))
+        .collect();
+
+    check_names(&mut report, "hazard", &hazards, templates);
+    check_names(&mut report, "category", &categories, templates);
+
+    for hazard in &hazards {
+        check_hazard(&mut report, graph, hazard, &range);
+    }
+
+    for category in &categories {
+        if !hazards
+            .iter()
+            .any(|hazard| hazard.iris(&sho("hasCategory")).any(|c| c == category.id))
+        {
+            report.push(
+                Severity::Warning,
+                &category.id,
+                "category without hazards".to_owned(),
+            );
+        }
+    }
+
+    report
+}
+
+/// Checks the consistency of an ontology
+///
+/// The names are checked against the identifiers of the given templates.
+pub fn check_ontology(
+    ontology_path: &Path,
+    format: Option<Format>,
+    templates: &[Templates],
+) -> Result<CheckReport> {
+    let graph = Graph::open(ontology_path, format)?;
+
+    Ok(check_graph(&graph, templates))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn problems() {
+        let graph = Graph::read(
+            Format::Turtle,
+            r#"
+            @prefix sho: <https://purl.org/sifis/hazards#> .
+
+            sho:Safety a sho:Category ; sho:description "Safety" .
+            sho:Privacy a sho:Category ; sho:description "Privacy" .
+            sho:FireHazard a sho:Hazard ;
+                sho:description "Fire" ;
+                sho:hasCategory sho:Safety ;
+                sho:riskScore 11 .
+            sho:firehazard a sho:Hazard ;
+                sho:hasCategory sho:Health .
+            sho:type a sho:Hazard ;
+                sho:description "Keyword" ;
+                sho:hasCategory sho:Safety ;
+                sho:riskScore "" .
+            "#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let report = check_graph(&graph, &[Templates::Rust]);
+        let messages: Vec<_> = report
+            .problems
+            .iter()
+            .map(|problem| (problem.severity, problem.message.as_str()))
+            .collect();
+
+        assert!(report.has_errors());
+        assert_eq!(
+            messages,
+            [
+                (
+                    Severity::Warning,
+                    "risk score range not found, using the default one"
+                ),
+                (Severity::Error, "missing description"),
+                (
+                    Severity::Error,
+                    "hazard name `firehazard` already used by `https://purl.org/sifis/hazards#FireHazard`"
+                ),
+                (
+                    Severity::Error,
+                    "`type` is not a valid identifier for the Rust template"
+                ),
+                (Severity::Error, "risk score 11 out of the range [0, 10]"),
+                (
+                    Severity::Error,
+                    "`hasCategory` references `https://purl.org/sifis/hazards#Health`, not a category"
+                ),
+                (Severity::Warning, "category without hazards"),
+            ]
+        );
+        assert!(report.to_string().ends_with("5 errors, 2 warnings\n"));
+    }
+
+    #[test]
+    fn shipped_ontology() {
+        let report = check_ontology(
+            Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/ontology.jsonld")),
+            None,
+            &[Templates::Rust],
+        )
+        .unwrap();
+
+        assert_eq!(report.problems, []);
+    }
+}
@@ -0,0 +1,373 @@
+//! Differences between two versions of the ontology.
+//!
+//! Each change is classified by its impact on the generated `Hazard` and
+//! `Category` enums. The enums are exhaustive, so adding a variant breaks
+//! the downstream `match` expressions as much as removing one.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::ontology::{CategoryData, HazardData, LocalizedText, Ontology};
+use crate::rdf::Format;
+
+/// Kind of ontology individual
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Item {
+    Hazard,
+    Category,
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Hazard => "hazard",
+            Self::Category => "category",
+        })
+    }
+}
+
+/// Change between two versions of the ontology
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Added {
+        item: Item,
+        name: String,
+    },
+    Removed {
+        item: Item,
+        name: String,
+    },
+    Renamed {
+        item: Item,
+        from: String,
+        to: String,
+    },
+    /// A hazard moved to another category.
+    Moved {
+        hazard: String,
+        from: String,
+        to: String,
+    },
+    DescriptionChanged {
+        item: Item,
+        name: String,
+    },
+}
+
+impl Change {
+    /// Returns whether the change breaks the code using the generated enums.
+    pub fn is_breaking(&self) -> bool {
+        !matches!(self, Self::DescriptionChanged { .. })
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Added { item, name } => write!(f, "{} `{}` added", item, name),
+            Self::Removed { item, name } => write!(f, "{} `{}` removed", item, name),
+            Self::Renamed { item, from, to } => {
+                write!(f, "{} `{}` renamed to `{}`", item, from, to)
+            }
+            Self::Moved { hazard, from, to } => write!(
+                f,
+                "hazard `{}` moved from category `{}` to `{}`",
+                hazard, from, to
+            ),
+            Self::DescriptionChanged { item, name } => {
+                write!(f, "description of {} `{}` changed", item, name)
+            }
+        }
+    }
+}
+
+/// Version bump required by a set of changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SemverBump {
+    None,
+    Patch,
+    Major,
+}
+
+impl fmt::Display for SemverBump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Patch => "patch",
+            Self::Major => "major",
+        })
+    }
+}
+
+/// Differences between two versions of the ontology
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiffReport {
+    pub changes: Vec<Change>,
+}
+
+impl DiffReport {
+    /// Returns whether any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(Change::is_breaking)
+    }
+
+    /// Returns the version bump required by the changes.
+    pub fn semver_bump(&self) -> SemverBump {
+        if self.is_breaking() {
+            SemverBump::Major
+        } else if self.changes.is_empty() {
+            SemverBump::None
+        } else {
+            SemverBump::Patch
+        }
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            let impact = if change.is_breaking() {
+                "breaking"
+            } else {
+                "non-breaking"
+            };
+            writeln!(f, "{}: {}", impact, change)?;
+        }
+        writeln!(f, "Suggested version bump: {}", self.semver_bump())?;
+
+        // Hazards are identified by name in the lockfile
+        let renames: Vec<_> = self
+            .changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::Renamed {
+                    item: Item::Hazard,
+                    from,
+                    to,
+                } => Some((from, to)),
+                _ => None,
+            })
+            .collect();
+        if !renames.is_empty() {
+            writeln!(
+                f,
+                "Renamed hazards get a new bit in `HazardSet`, unless recorded \
+                 in the lockfile:\n\n[renamed]"
+            )?;
+            for (from, to) in renames {
+                writeln!(f, "{} = \"{}\"", from, to)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Common accessors of hazards and categories.
+trait Individual {
+    fn name(&self) -> &str;
+    fn descriptions(&self) -> &[LocalizedText];
+}
+
+impl Individual for HazardData {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn descriptions(&self) -> &[LocalizedText] {
+        &self.descriptions
+    }
+}
+
+impl Individual for CategoryData {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn descriptions(&self) -> &[LocalizedText] {
+        &self.descriptions
+    }
+}
+
+// Compares two lists of individuals, adding the changes and returning the
+// pairs of matching old and new individuals.
+//
+// An individual removed and another one added with the same non-empty
+// descriptions are considered a rename.
+fn compare<'a, T: Individual>(
+    item: Item,
+    old: &'a [T],
+    new: &'a [T],
+    changes: &mut Vec<Change>,
+) -> Vec<(&'a T, &'a T)> {
+    let find = |list: &'a [T], name: &str| list.iter().find(|v| v.name() == name);
+
+    let mut pairs = Vec::new();
+    let mut removed = Vec::new();
+    for old in old {
+        match find(new, old.name()) {
+            Some(new) => pairs.push((old, new)),
+            None => removed.push(old),
+        }
+    }
+    let mut added: Vec<_> = new
+        .iter()
+        .filter(|new| find(old, new.name()).is_none())
+        .collect();
+
+    for old in removed {
+        let renamed = added.iter().position(|new| {
+            !old.descriptions().is_empty() && new.descriptions() == old.descriptions()
+        });
+        match renamed {
+            Some(i) => {
+                let new = added.remove(i);
+                changes.push(Change::Renamed {
+                    item,
+                    from: old.name().to_owned(),
+                    to: new.name().to_owned(),
+                });
+                pairs.push((old, new));
+            }
+            None => changes.push(Change::Removed {
+                item,
+                name: old.name().to_owned(),
+            }),
+        }
+    }
+    changes.extend(added.into_iter().map(|new| Change::Added {
+        item,
+        name: new.name().to_owned(),
+    }));
+
+    for (old, new) in &pairs {
+        if old.descriptions() != new.descriptions() {
+            changes.push(Change::DescriptionChanged {
+                item,
+                name: new.name().to_owned(),
+            });
+        }
+    }
+
+    pairs
+}
+
+/// Compares two versions of the ontology.
+pub(crate) fn diff(old: &Ontology, new: &Ontology) -> DiffReport {
+    let mut changes = Vec::new();
+
+    let categories: HashMap<_, _> = compare(
+        Item::Category,
+        &old.categories,
+        &new.categories,
+        &mut changes,
+    )
+    .into_iter()
+    .map(|(old, new)| (old.name.as_str(), new.name.as_str()))
+    .collect();
+
+    for (old, new) in compare(Item::Hazard, &old.hazards, &new.hazards, &mut changes) {
+        // Category renames are reported once, not as moves of their hazards
+        let category = categories.get(old.category.as_str()).copied();
+        if category != Some(new.category.as_str()) {
+            changes.push(Change::Moved {
+                hazard: new.name.clone(),
+                from: old.category.clone(),
+                to: new.category.clone(),
+            });
+        }
+    }
+
+    DiffReport { changes }
+}
+
+/// Compares two versions of an ontology
+///
+/// The format of each file is detected from its extension when not given.
+pub fn diff_ontologies(old: &Path, new: &Path, format: Option<Format>) -> Result<DiffReport> {
+    let old = Ontology::open(old, format)?;
+    let new = Ontology::open(new, format)?;
+
+    Ok(diff(&old, &new))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::Graph;
+
+    fn read(document: &str) -> Ontology {
+        let prefix = "@prefix sho: <https://purl.org/sifis/hazards#> .\n";
+        let graph = Graph::read(Format::Turtle, format!("{}{}", prefix, document).as_bytes());
+        Ontology::from_graph(&graph.unwrap()).unwrap()
+    }
+
+    #[test]
+    fn changes() {
+        let old = read(
+            r#"
+            sho:Safety a sho:Category ; sho:description "Safety" .
+            sho:Financial a sho:Category ; sho:description "Money" .
+            sho:Fire a sho:Hazard ; sho:description "Fire" ; sho:hasCategory sho:Safety .
+            sho:Smoke a sho:Hazard ; sho:description "Smoke" ; sho:hasCategory sho:Safety .
+            sho:Spend a sho:Hazard ; sho:description "Spend" ; sho:hasCategory sho:Financial .
+            sho:Flood a sho:Hazard ; sho:description "Flood" ; sho:hasCategory sho:Safety .
+            "#,
+        );
+
+        let report = diff(&old, &old);
+        assert_eq!(report.changes, []);
+        assert_eq!(report.semver_bump(), SemverBump::None);
+
+        let new = read(
+            r#"
+            sho:Safety a sho:Category ; sho:description "Physical safety" .
+            sho:Economic a sho:Category ; sho:description "Money" .
+            sho:FireHazard a sho:Hazard ; sho:description "Fire" ; sho:hasCategory sho:Safety .
+            sho:Smoke a sho:Hazard ; sho:description "Smoke" ; sho:hasCategory sho:Economic .
+            sho:Spend a sho:Hazard ; sho:description "Spend" ; sho:hasCategory sho:Economic .
+            sho:Gas a sho:Hazard ; sho:description "Gas" ; sho:hasCategory sho:Safety .
+            "#,
+        );
+
+        let report = diff(&old, &new);
+        let changes: Vec<_> = report.changes.iter().map(Change::to_string).collect();
+        assert_eq!(
+            changes,
+            [
+                "category `Financial` renamed to `Economic`",
+                "description of category `Safety` changed",
+                "hazard `Fire` renamed to `FireHazard`",
+                "hazard `Flood` removed",
+                "hazard `Gas` added",
+                "hazard `Smoke` moved from category `Safety` to `Economic`",
+            ]
+        );
+        assert_eq!(report.semver_bump(), SemverBump::Major);
+        assert!(report.to_string().ends_with(
+            "Suggested version bump: major\n\
+             Renamed hazards get a new bit in `HazardSet`, unless recorded in the lockfile:\n\n\
+             [renamed]\n\
+             Fire = \"FireHazard\"\n"
+        ));
+
+        let new = read(
+            r#"
+            sho:Safety a sho:Category ; sho:description "Safety" .
+            sho:Financial a sho:Category ; sho:description "Money" .
+            sho:Fire a sho:Hazard ; sho:description "Fire!" ; sho:hasCategory sho:Safety .
+            sho:Smoke a sho:Hazard ; sho:description "Smoke" ; sho:hasCategory sho:Safety .
+            sho:Spend a sho:Hazard ; sho:description "Spend" ; sho:hasCategory sho:Financial .
+            sho:Flood a sho:Hazard ; sho:description "Flood" ; sho:hasCategory sho:Safety .
+            "#,
+        );
+        let report = diff(&old, &new);
+        assert!(!report.is_breaking());
+        assert_eq!(report.semver_bump(), SemverBump::Patch);
+        assert!(report
+            .to_string()
+            .starts_with("non-breaking: description of hazard `Fire` changed\n"));
+    }
+}
@@ -1,5 +1,396 @@
 use minijinja::{Error, State};
 
+// Filters have to return `minijinja::Error`, which is a large type.
+#[allow(clippy::result_large_err)]
 pub(crate) fn hypens_to_underscores(_state: &State, value: String) -> Result<String, Error> {
     Ok(value.replace('-', "_"))
 }
+
+// Splits a name into its words, at separators and at case changes.
+fn words(value: &str) -> Vec<String> {
+    let chars: Vec<_> = value.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let boundary = word.chars().last().is_some_and(|prev| {
+            c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_numeric()
+                    || chars.get(i + 1).is_some_and(|next| next.is_lowercase()))
+        });
+        if boundary {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+// Joins the words of a name capitalized, lowering the first one if requested.
+fn camel_words(value: &str, lower_first: bool) -> String {
+    words(value)
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let word = word.to_lowercase();
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if i > 0 || !lower_first => first.to_uppercase().chain(chars).collect(),
+                _ => word,
+            }
+        })
+        .collect()
+}
+
+#[allow(clippy::result_large_err)]
+pub(crate) fn snake_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(words(&value).join("_").to_lowercase())
+}
+
+#[allow(clippy::result_large_err)]
+pub(crate) fn screaming_snake_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(words(&value).join("_").to_uppercase())
+}
+
+#[allow(clippy::result_large_err)]
+pub(crate) fn kebab_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(words(&value).join("-").to_lowercase())
+}
+
+#[allow(clippy::result_large_err)]
+pub(crate) fn camel_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(camel_words(&value, true))
+}
+
+#[allow(clippy::result_large_err)]
+pub(crate) fn pascal_case(_state: &State, value: String) -> Result<String, Error> {
+    Ok(camel_words(&value, false))
+}
+
+// Escapes the content of a double-quoted string literal. Quotes, backslashes
+// and the common control characters are escaped the same way by every
+// language, `escape_other` escapes the remaining control characters, all of
+// them below U+00A0.
+fn escape(value: &str, escape_other: impl Fn(char) -> String) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&escape_other(c)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn escape_rust_str(value: &str) -> String {
+    escape(value, |c| format!("\\u{{{:x}}}", c as u32))
+}
+
+fn escape_c_str(value: &str) -> String {
+    // Octal escapes have at most three digits, so they cannot absorb the
+    // characters following them as hexadecimal ones do
+    let escaped = escape(value, |c| format!("\\{:03o}", c as u32));
+    // Breaks the `??` sequences which might start a trigraph
+    escaped.replace("??", "?\\?")
+}
+
+fn escape_json_str(value: &str) -> String {
+    escape(value, |c| format!("\\u{:04x}", c as u32))
+        // Line terminators in JavaScript string literals before ES2019
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+fn escape_python_str(value: &str) -> String {
+    escape(value, |c| format!("\\x{:02x}", c as u32))
+}
+
+// Splits a text into lines, which can end with `\n`, `\r\n` or a lone `\r`.
+fn split_lines(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split("\r\n")
+        .flat_map(|line| line.split(['\n', '\r']))
+}
+
+// Escapes a text to be placed within Markdown. HTML is written as text and
+// the characters which would start an emphasis, a code span, a link or a
+// heading are backslash-escaped, as well as the markers at the start of a
+// line which would begin a list, a quote or a heading. Lines are unindented
+// so they cannot become code blocks.
+fn escape_markdown_str(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (i, line) in split_lines(value).enumerate() {
+        if i > 0 {
+            escaped.push('\n');
+        }
+        let line = line.trim();
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        // Position of the character marking a list item or a heading
+        let marker = match line[digits..].chars().next() {
+            Some('-' | '+' | '=') if digits == 0 => Some(0),
+            Some('.' | ')') if digits > 0 => Some(digits),
+            _ => None,
+        };
+        for (i, c) in line.char_indices() {
+            match c {
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '&' => escaped.push_str("&amp;"),
+                '\\' | '`' | '*' | '_' | '[' | ']' | '#' | '|' | '~' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                c if Some(i) == marker => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                c => escaped.push(c),
+            }
+        }
+    }
+
+    escaped
+}
+
+// Joins the lines of a text with spaces, for the contexts which cannot
+// span several lines, such as a list item.
+fn fold_lines_str(value: &str) -> String {
+    split_lines(value)
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escapes a string to be placed within a Rust string literal.
+#[allow(clippy::result_large_err)]
+pub(crate) fn escape_rust(_state: &State, value: String) -> Result<String, Error> {
+    Ok(escape_rust_str(&value))
+}
+
+/// Escapes a string to be placed within a C string literal.
+#[allow(clippy::result_large_err)]
+pub(crate) fn escape_c(_state: &State, value: String) -> Result<String, Error> {
+    Ok(escape_c_str(&value))
+}
+
+/// Escapes a string to be placed within a JSON, or JavaScript, string.
+#[allow(clippy::result_large_err)]
+pub(crate) fn escape_json(_state: &State, value: String) -> Result<String, Error> {
+    Ok(escape_json_str(&value))
+}
+
+/// Escapes a string to be placed within a Python string literal.
+#[allow(clippy::result_large_err)]
+pub(crate) fn escape_python(_state: &State, value: String) -> Result<String, Error> {
+    Ok(escape_python_str(&value))
+}
+
+/// Escapes a string to be placed within Markdown text.
+#[allow(clippy::result_large_err)]
+pub(crate) fn escape_markdown(_state: &State, value: String) -> Result<String, Error> {
+    Ok(escape_markdown_str(&value))
+}
+
+/// Joins the lines of a string, to be placed within a single line.
+#[allow(clippy::result_large_err)]
+pub(crate) fn fold_lines(_state: &State, value: String) -> Result<String, Error> {
+    Ok(fold_lines_str(&value))
+}
+
+// Wraps a text into lines of at most `width` characters, prefix included.
+//
+// Line breaks of the text are kept, words longer than a line are not split.
+fn wrap(value: &str, prefix: &str, width: usize) -> String {
+    let prefix_width = prefix.chars().count();
+    let mut lines = Vec::new();
+
+    for paragraph in value.lines() {
+        let mut line = String::new();
+        let mut line_width = prefix_width;
+        for word in paragraph.split_whitespace() {
+            let word_width = word.chars().count();
+            if !line.is_empty() && line_width + 1 + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = prefix_width;
+            }
+            if !line.is_empty() {
+                line.push(' ');
+                line_width += 1;
+            }
+            line.push_str(word);
+            line_width += word_width;
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+
+    lines
+        .iter()
+        .map(|line| format!("{}{}", prefix, line).trim_end().to_owned())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Wraps a text into comment lines starting with `prefix`, such as
+/// `"    /// "`, and at most `width` characters long.
+#[allow(clippy::result_large_err)]
+pub(crate) fn wrap_comment(
+    _state: &State,
+    value: String,
+    prefix: String,
+    width: usize,
+) -> Result<String, Error> {
+    Ok(wrap(&value, &prefix, width))
+}
+
+// Keeps a text within a comment: `*/` and `/*` are broken apart, so they
+// can neither end nor open a block comment, as well as the `??/` trigraph,
+// which would join a line to the next one in C, and the continuation lines
+// start with `prefix`, since Python and C treat a lone carriage return as
+// a line break too.
+fn comment_str(value: &str, prefix: &str) -> String {
+    let value = value
+        .replace("*/", "* /")
+        .replace("/*", "/ *")
+        .replace("??/", "?? /");
+    let mut lines = split_lines(&value);
+    let mut comment = lines.next().unwrap_or_default().to_owned();
+    for line in lines {
+        comment.push('\n');
+        comment.push_str(format!("{}{}", prefix, line).trim_end());
+    }
+
+    comment
+}
+
+/// Makes a text safe to be placed within a comment, whose continuation
+/// lines start with `prefix`, such as `"     * "`.
+#[allow(clippy::result_large_err)]
+pub(crate) fn comment(_state: &State, value: String, prefix: String) -> Result<String, Error> {
+    Ok(comment_str(&value, &prefix))
+}
+
+fn local_name(iri: &str) -> &str {
+    iri.rsplit(['#', '/']).next().unwrap_or(iri)
+}
+
+/// Returns the local name of an IRI, the part after its last `#` or `/`.
+#[allow(clippy::result_large_err)]
+pub(crate) fn iri_local_name(_state: &State, value: String) -> Result<String, Error> {
+    Ok(local_name(&value).to_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn case() {
+        assert_eq!(words("AudioVideoStream"), ["Audio", "Video", "Stream"]);
+        assert_eq!(words("HTTPServer2Go"), ["HTTP", "Server2", "Go"]);
+        assert_eq!(words("fire-hazard level"), ["fire", "hazard", "level"]);
+        assert_eq!(words("Safety").join("_").to_uppercase(), "SAFETY");
+        assert_eq!(camel_words("AudioVideoStream", true), "audioVideoStream");
+        assert_eq!(camel_words("HTTPServer2Go", true), "httpServer2Go");
+        assert_eq!(camel_words("fire-hazard level", false), "FireHazardLevel");
+        assert_eq!(camel_words("", true), "");
+    }
+
+    #[test]
+    fn escaping() {
+        let text = "A \"quoted\"\\path\n\ttab\u{7}bell ??= né\u{2028}";
+
+        assert_eq!(
+            escape_rust_str(text),
+            r#"A \"quoted\"\\path\n\ttab\u{7}bell ??= né"#.to_owned() + "\u{2028}"
+        );
+        assert_eq!(
+            escape_c_str(text),
+            r#"A \"quoted\"\\path\n\ttab\007bell ?\?= né"#.to_owned() + "\u{2028}"
+        );
+        assert_eq!(
+            escape_json_str(text),
+            r#"A \"quoted\"\\path\n\ttab\u0007bell ??= né\u2028"#
+        );
+        assert_eq!(
+            escape_python_str(text),
+            r#"A \"quoted\"\\path\n\ttab\x07bell ??= né"#.to_owned() + "\u{2028}"
+        );
+    }
+
+    #[test]
+    fn commenting() {
+        assert_eq!(
+            comment_str("Ends */ here ??/\nopens /* and\r\n\rlast */*/", "     * "),
+            "Ends * / here ?? /\n     * opens / * and\n     *\n     * last * / * /"
+        );
+        assert_eq!(comment_str("One line", "# "), "One line");
+        assert_eq!(comment_str("", "# "), "");
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            escape_markdown_str("<b>bold</b> & *em* [link](x) `code` _a_ #1 \\ | ~"),
+            "&lt;b&gt;bold&lt;/b&gt; &amp; \\*em\\* \\[link\\](x) \\`code\\` \\_a\\_ \\#1 \\\\ \\| \\~"
+        );
+        assert_eq!(
+            escape_markdown_str("Start\n- item\r\n    2. second\r+ plus\n= setext\n> quote"),
+            "Start\n\\- item\n2\\. second\n\\+ plus\n\\= setext\n&gt; quote"
+        );
+        assert_eq!(escape_markdown_str("1-2 2024. a-b"), "1-2 2024. a-b");
+        assert_eq!(
+            fold_lines_str("First\n\n  second\r\nthird\r"),
+            "First second third"
+        );
+    }
+
+    #[test]
+    fn wrapping() {
+        let text = "The execution authorises the app to get and save information \
+                    about the app's energy impact";
+
+        assert_eq!(
+            wrap(text, "    /// ", 40),
+            "    /// The execution authorises the app\n    \
+             /// to get and save information\n    \
+             /// about the app's energy impact"
+        );
+        assert_eq!(wrap("First\n\nSecond", "# ", 80), "# First\n#\n# Second");
+        assert_eq!(wrap("", "/// ", 80), "///");
+        assert_eq!(
+            wrap("Averyveryverylongword and", "// ", 10),
+            "// Averyveryverylongword\n// and"
+        );
+    }
+
+    #[test]
+    fn iri() {
+        assert_eq!(
+            local_name("https://purl.org/sifis/hazards#FireHazard"),
+            "FireHazard"
+        );
+        assert_eq!(local_name("http://schema.org/Person"), "Person");
+        assert_eq!(local_name("FireHazard"), "FireHazard");
+    }
+}
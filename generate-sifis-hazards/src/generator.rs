@@ -0,0 +1,541 @@
+//! Library entry point of the generator.
+//!
+//! A [`Generator`] renders the files of a template in memory, so they can
+//! be written to any directory, including the `OUT_DIR` of a build script,
+//! or compared with the files already committed.
+//!
+//! ```no_run
+//! // build.rs
+//! use generate_sifis_hazards::{Generator, Templates};
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     Generator::open(Templates::Rust, "ontology.jsonld".as_ref(), None)?
+//!         .for_include(Some("crate::thing::Hazard"))
+//!         .write_to_out_dir()?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The crate then includes the generated API in a module with
+//! `include!(concat!(env!("OUT_DIR"), "/src/ontology.rs"));`, see
+//! [`Generator::for_include`].
+//!
+//! The bit of each hazard in a `HazardSet` is recorded in a lockfile, by
+//! default the ontology path with the `lock` extension, so it stays the same
+//! across regenerations. The lockfile is updated by [`Generator::write`] and
+//! should be committed along with the ontology.
+//!
+//! A renamed hazard gets a new bit unless its old name is recorded in the
+//! `[renamed]` table of the lockfile, see [`Generator::rename_warning`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, create_dir_all};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use minijinja::value::Value;
+use tracing::debug;
+
+use crate::lockfile::Lockfile;
+use crate::ontology::Ontology;
+use crate::rdf::Format;
+use crate::template_dir::TemplateDir;
+use crate::toolchain::*;
+use crate::{BuildTemplate, Templates};
+
+/// Error returned when generated files differ from the rendered ones.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaleFiles {
+    /// Paths of the missing or outdated files.
+    pub files: Vec<PathBuf>,
+}
+
+impl fmt::Display for StaleFiles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let files: Vec<_> = self
+            .files
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        write!(
+            f,
+            "Generated files are out of date, regenerate them from the ontology: {}",
+            files.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for StaleFiles {}
+
+// Templates rendered by a generator.
+#[derive(Clone, Debug)]
+enum Template {
+    Builtin(Templates),
+    Dir(TemplateDir),
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Builtin(template) => write!(f, "the {} template", template),
+            Self::Dir(dir) => write!(f, "the templates of {}", dir),
+        }
+    }
+}
+
+/// Generator of the hazards code for a template.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    template: Template,
+    ontology_path: PathBuf,
+    ontology: Ontology,
+    lockfile_path: PathBuf,
+    lockfile: Lockfile,
+    /// Hazards which got a new bit.
+    new_bits: Vec<String>,
+    for_include: bool,
+    thing_hazard: Option<String>,
+}
+
+impl Generator {
+    fn new(template: Template, ontology_path: &Path, format: Option<Format>) -> Result<Self> {
+        let ontology = Ontology::open(ontology_path, format)?;
+        let lockfile_path = ontology_path.with_extension("lock");
+        let mut lockfile = Lockfile::open(&lockfile_path)?;
+        let new_bits = lockfile.update(&ontology.hazards)?;
+
+        Ok(Self {
+            template,
+            ontology_path: ontology_path.to_path_buf(),
+            ontology,
+            lockfile_path,
+            lockfile,
+            new_bits,
+            for_include: false,
+            thing_hazard: Some("crate::thing::Hazard".to_owned()),
+        })
+    }
+
+    /// Reads the ontology to generate the code for a template.
+    ///
+    /// The ontology format is detected from the file extension when not given.
+    pub fn open(template: Templates, ontology_path: &Path, format: Option<Format>) -> Result<Self> {
+        Self::new(Template::Builtin(template), ontology_path, format)
+    }
+
+    /// Reads the ontology to generate the code for the templates of a
+    /// directory.
+    ///
+    /// The directory contains the templates and a `templates.toml` manifest
+    /// mapping each generated file to the template rendering it:
+    ///
+    /// ```toml
+    /// [files]
+    /// "hazards/hazards.go" = "hazards.go.j2"
+    /// ```
+    ///
+    /// The templates receive the same context and filters as the built-in
+    /// ones.
+    pub fn open_template_dir(
+        template_dir: &Path,
+        ontology_path: &Path,
+        format: Option<Format>,
+    ) -> Result<Self> {
+        Self::new(
+            Template::Dir(TemplateDir::open(template_dir)?),
+            ontology_path,
+            format,
+        )
+    }
+
+    /// Uses another lockfile for the bits of the hazards.
+    pub fn with_lockfile(mut self, lockfile_path: &Path) -> Result<Self> {
+        let mut lockfile = Lockfile::open(lockfile_path)?;
+        self.new_bits = lockfile.update(&self.ontology.hazards)?;
+        self.lockfile_path = lockfile_path.to_path_buf();
+        self.lockfile = lockfile;
+
+        Ok(self)
+    }
+
+    /// Renders the Rust API so that it can be included with `include!`.
+    ///
+    /// The API has no inner attributes nor test module, and converts the
+    /// hazards of the given Thing hazard type, which has `id` and `name`
+    /// string fields, or none at all when no path is given. The API of the
+    /// sifis crate converts the `crate::thing::Hazard`s instead, only when
+    /// its `serde` feature is enabled.
+    pub fn for_include(mut self, thing_hazard: Option<&str>) -> Self {
+        self.for_include = true;
+        self.thing_hazard = thing_hazard.map(str::to_owned);
+        self
+    }
+
+    /// Returns the path of the lockfile.
+    pub fn lockfile_path(&self) -> &Path {
+        &self.lockfile_path
+    }
+
+    /// Renders the lockfile, including the bits of new hazards.
+    pub fn render_lockfile(&self) -> String {
+        self.lockfile.render()
+    }
+
+    /// Returns a warning when some hazards got a new bit while others left
+    /// the ontology, which is how renamed hazards look like.
+    pub fn rename_warning(&self) -> Option<String> {
+        let removed = self.lockfile.removed(&self.ontology.hazards);
+        if self.new_bits.is_empty() || removed.is_empty() {
+            return None;
+        }
+
+        let names = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let new_bits: Vec<_> = self.new_bits.iter().map(String::as_str).collect();
+        Some(format!(
+            "{} got a new bit while {} left the ontology: record the renamed hazards \
+             in the `[renamed]` table of {} to keep their bits, \
+             e.g. `{} = \"{}\"`",
+            names(&new_bits),
+            names(&removed),
+            self.lockfile_path.display(),
+            removed[0],
+            new_bits[0]
+        ))
+    }
+
+    /// Renders the files of the template, keyed by their path relative to
+    /// the output directory.
+    pub fn render(&self) -> Result<BTreeMap<PathBuf, String>> {
+        let mut template = match &self.template {
+            Template::Builtin(Templates::Rust) => Rust::create().build(&self.ontology),
+            Template::Builtin(Templates::C) => C::create().build(&self.ontology),
+            Template::Builtin(Templates::Python) => Python::create().build(&self.ontology),
+            Template::Builtin(Templates::TypeScript) => TypeScript::create().build(&self.ontology),
+            Template::Builtin(Templates::Markdown) => Markdown::create().build(&self.ontology),
+            Template::Builtin(Templates::Html) => Html::create().build(&self.ontology),
+            Template::Builtin(Templates::JsonSchema) => JsonSchema::create().build(&self.ontology),
+            Template::Dir(dir) => dir.build(&self.ontology),
+        };
+        template.context.insert(
+            "hazard_bits".to_string(),
+            Value::from_serializable(&self.lockfile.hazards),
+        );
+        template
+            .context
+            .insert("for_include".to_string(), Value::from(self.for_include));
+        template.context.insert(
+            "thing_hazard".to_string(),
+            Value::from_serializable(&self.thing_hazard),
+        );
+
+        template.render()
+    }
+
+    /// Renders a single file of the template, given its relative path.
+    pub fn render_file(&self, path: &Path) -> Result<String> {
+        self.render()?
+            .remove(path)
+            .ok_or_else(|| anyhow!("{} is not generated by {}", path.display(), self.template))
+    }
+
+    fn write_files(&self, output_path: &Path) -> Result<()> {
+        for (path, content) in self.render()? {
+            let path = output_path.join(path);
+            if let Some(dir) = path.parent() {
+                create_dir_all(dir)?;
+            }
+            debug!("Creating {}", path.display());
+            fs::write(path, content)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the files of the template into the output directory and
+    /// updates the lockfile.
+    pub fn write(&self, output_path: &Path) -> Result<()> {
+        self.write_files(output_path)?;
+
+        let lockfile = self.render_lockfile();
+        if fs::read_to_string(&self.lockfile_path).ok() != Some(lockfile.clone()) {
+            debug!("Updating {}", self.lockfile_path.display());
+            fs::write(&self.lockfile_path, lockfile)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the files of the template into the `OUT_DIR` of a build script
+    /// and returns its path.
+    ///
+    /// Cargo is asked to run the build script again when the ontology or
+    /// the lockfile change. Build scripts must not modify the sources, so
+    /// the lockfile is not updated.
+    pub fn write_to_out_dir(&self) -> Result<PathBuf> {
+        let out_dir = std::env::var_os("OUT_DIR")
+            .map(PathBuf::from)
+            .context("OUT_DIR is not set, the generator is not run by a build script")?;
+
+        println!("cargo:rerun-if-changed={}", self.ontology_path.display());
+        println!("cargo:rerun-if-changed={}", self.lockfile_path.display());
+        if let Some(warning) = self.rename_warning() {
+            println!("cargo:warning={}", warning);
+        }
+        self.write_files(&out_dir)?;
+
+        Ok(out_dir)
+    }
+
+    /// Returns the files of the output directory which are missing or
+    /// differ from the rendered ones, followed by the lockfile if it is
+    /// outdated.
+    pub fn stale_files(&self, output_path: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for (path, content) in self.render()? {
+            let path = output_path.join(path);
+            if fs::read_to_string(&path).ok() != Some(content) {
+                files.push(path);
+            }
+        }
+        if fs::read_to_string(&self.lockfile_path).ok() != Some(self.render_lockfile()) {
+            files.push(self.lockfile_path.clone());
+        }
+
+        Ok(files)
+    }
+
+    /// Checks that the files of the output directory are up to date,
+    /// returning a [`StaleFiles`] error otherwise.
+    pub fn check(&self, output_path: &Path) -> Result<()> {
+        let files = self.stale_files(output_path)?;
+        if files.is_empty() {
+            Ok(())
+        } else {
+            Err(StaleFiles { files }.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::process::Command;
+
+    use super::*;
+
+    fn generator() -> Generator {
+        let path = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/ontology.jsonld"));
+        Generator::open(Templates::Rust, path, None).unwrap()
+    }
+
+    #[test]
+    fn render() {
+        let generator = generator();
+
+        let api = generator.render_file(Path::new("src/ontology.rs")).unwrap();
+        assert!(api.contains("pub enum Hazard"));
+        assert!(api.contains("    pub const ALL: [Self; 22] = [\n        Self::AirPoisoning,\n"));
+        assert!(api.contains("    pub fn iter() -> std::array::IntoIter<Self, 3> {\n"));
+        assert!(generator.render_file(Path::new("ontology.rs")).is_err());
+
+        let header = Generator {
+            template: Template::Builtin(Templates::C),
+            ..generator.clone()
+        }
+        .render_file(Path::new("include/sifis_hazards.h"))
+        .unwrap();
+        assert!(header.contains("    SIFIS_HAZARD_FIRE_HAZARD,\n"));
+        assert!(header.contains("    SIFIS_CATEGORY_SAFETY,\n"));
+
+        let catalogue = Generator {
+            template: Template::Builtin(Templates::Html),
+            ..generator.clone()
+        }
+        .render_file(Path::new("docs/hazards.html"))
+        .unwrap();
+        assert!(catalogue.contains("<h3>FireHazard</h3>"));
+        assert!(catalogue.contains("about the app&#x27;s duration of use"));
+
+        let schemas = Generator {
+            template: Template::Builtin(Templates::JsonSchema),
+            ..generator.clone()
+        }
+        .render()
+        .unwrap();
+        let schema: serde_json::Value =
+            serde_json::from_str(&schemas[Path::new("schemas/hazards.schema.json")]).unwrap();
+        let openapi: serde_json::Value =
+            serde_json::from_str(&schemas[Path::new("schemas/openapi.json")]).unwrap();
+        assert_eq!(schema["$defs"], openapi["components"]["schemas"]);
+        assert_eq!(schema["$defs"]["Hazard"]["enum"][0], "AirPoisoning");
+        assert_eq!(
+            schema["$defs"]["Hazard"]["oneOf"][0]["x-category"],
+            "Safety"
+        );
+        assert_eq!(schema["$defs"]["RiskScore"]["maximum"], 10);
+    }
+
+    #[test]
+    fn project_structures() {
+        let generator = generator();
+        let files = |template| {
+            let generator = Generator {
+                template: Template::Builtin(template),
+                ..generator.clone()
+            };
+            let files = generator.render().unwrap();
+            for (path, content) in &files {
+                assert!(content.ends_with('\n'), "{}", path.display());
+            }
+            files.into_keys().collect::<Vec<_>>()
+        };
+
+        assert_eq!(files(Templates::Rust), [Path::new("src/ontology.rs")]);
+        assert_eq!(files(Templates::C), [Path::new("include/sifis_hazards.h")]);
+        assert_eq!(
+            files(Templates::Python),
+            [
+                Path::new("sifis_hazards/__init__.py"),
+                Path::new("sifis_hazards/hazards.py")
+            ]
+        );
+        assert_eq!(
+            files(Templates::TypeScript),
+            [Path::new("src/hazards.ts"), Path::new("src/index.ts")]
+        );
+        assert_eq!(files(Templates::Markdown), [Path::new("docs/hazards.md")]);
+        assert_eq!(files(Templates::Html), [Path::new("docs/hazards.html")]);
+        assert_eq!(
+            files(Templates::JsonSchema),
+            [
+                Path::new("schemas/hazards.schema.json"),
+                Path::new("schemas/openapi.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn committed_api() {
+        // The sifis crate ships the API generated from the same ontology
+        let sifis = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+
+        generator().check(sifis).unwrap();
+    }
+
+    #[test]
+    fn include() {
+        let dir = tempfile::tempdir().unwrap();
+        generator()
+            .for_include(Some("crate::thing::Hazard"))
+            .write_files(dir.path())
+            .unwrap();
+        let lib = dir.path().join("lib.rs");
+        fs::write(
+            &lib,
+            r#"
+            pub mod thing {
+                pub struct Hazard {
+                    pub id: String,
+                    pub name: String,
+                }
+            }
+
+            pub mod ontology {
+                include!(concat!(env!("OUT_DIR"), "/src/ontology.rs"));
+            }
+
+            pub fn fire() -> Option<ontology::Hazard> {
+                ontology::Hazard::has_hazard(&thing::Hazard {
+                    id: "sho:FireHazard".to_owned(),
+                    name: String::new(),
+                })
+            }
+            "#,
+        )
+        .unwrap();
+
+        let output = Command::new("rustc")
+            .args(["--edition", "2021", "--crate-type", "lib", "-D", "warnings"])
+            .arg("--out-dir")
+            .arg(dir.path())
+            .arg(&lib)
+            .env("OUT_DIR", dir.path())
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn check() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("src/ontology.rs");
+        // Keeps the committed lockfile untouched
+        let lockfile_path = dir.path().join("ontology.lock");
+        let generator = generator().with_lockfile(&lockfile_path).unwrap();
+
+        let err = generator.check(dir.path()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<StaleFiles>(),
+            Some(&StaleFiles {
+                files: vec![path.clone(), lockfile_path.clone()]
+            })
+        );
+
+        generator.write(dir.path()).unwrap();
+        assert!(generator.check(dir.path()).is_ok());
+        assert_eq!(
+            fs::read_to_string(&lockfile_path).unwrap(),
+            generator.render_lockfile()
+        );
+
+        fs::write(&path, "// Outdated").unwrap();
+        assert_eq!(
+            generator.stale_files(dir.path()).unwrap(),
+            std::slice::from_ref(&path)
+        );
+
+        fs::remove_file(&lockfile_path).unwrap();
+        assert_eq!(
+            generator.stale_files(dir.path()).unwrap(),
+            [path, lockfile_path]
+        );
+
+        generator.write(dir.path()).unwrap();
+        assert!(generator.check(dir.path()).is_ok());
+    }
+
+    #[test]
+    fn renamed_hazard() {
+        let dir = tempfile::tempdir().unwrap();
+        let lockfile_path = dir.path().join("ontology.lock");
+        let lockfile = generator()
+            .render_lockfile()
+            .replace("FireHazard = 6", "Fire = 6");
+        fs::write(&lockfile_path, &lockfile).unwrap();
+
+        let generator = generator().with_lockfile(&lockfile_path).unwrap();
+        assert!(generator.render_lockfile().contains("FireHazard = 22\n"));
+        let warning = generator.rename_warning().unwrap();
+        assert!(warning.starts_with("`FireHazard` got a new bit while `Fire` left the ontology"));
+        assert!(warning.ends_with("e.g. `Fire = \"FireHazard\"`"));
+
+        fs::write(
+            &lockfile_path,
+            format!("{}\n[renamed]\nFire = \"FireHazard\"\n", lockfile),
+        )
+        .unwrap();
+        let generator = generator.with_lockfile(&lockfile_path).unwrap();
+        assert!(generator.render_lockfile().contains("FireHazard = 6\n"));
+        assert_eq!(generator.rename_warning(), None);
+    }
+}
@@ -0,0 +1,185 @@
+//! Graph of RDF statements.
+//!
+//! Every input format is read into this graph, whose nodes are keyed by
+//! absolute IRIs or blank node identifiers.
+
+use std::collections::{BTreeMap, HashMap};
+
+pub(crate) const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+/// Object of a node property.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Object {
+    Iri(String),
+    Literal {
+        value: String,
+        lang: Option<String>,
+        datatype: Option<String>,
+    },
+    List(Vec<Object>),
+}
+
+impl Object {
+    pub(crate) fn as_iri(&self) -> Option<&str> {
+        match self {
+            Self::Iri(iri) => Some(iri),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_literal(&self) -> Option<(&str, Option<&str>)> {
+        match self {
+            Self::Literal { value, lang, .. } => Some((value, lang.as_deref())),
+            _ => None,
+        }
+    }
+}
+
+/// Node of the graph.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Node {
+    pub(crate) id: String,
+    pub(crate) types: Vec<String>,
+    pub(crate) properties: HashMap<String, Vec<Object>>,
+}
+
+impl Node {
+    pub(crate) fn has_type(&self, iri: &str) -> bool {
+        self.types.iter().any(|t| t == iri)
+    }
+
+    pub(crate) fn values(&self, property: &str) -> &[Object] {
+        self.properties
+            .get(property)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn iris<'a>(&'a self, property: &str) -> impl Iterator<Item = &'a str> {
+        self.values(property).iter().filter_map(Object::as_iri)
+    }
+
+    pub(crate) fn literals<'a>(
+        &'a self,
+        property: &str,
+    ) -> impl Iterator<Item = (&'a str, Option<&'a str>)> {
+        self.values(property).iter().filter_map(Object::as_literal)
+    }
+}
+
+/// Graph of nodes, in document order.
+#[derive(Debug, Default)]
+pub(crate) struct Graph {
+    nodes: Vec<Node>,
+    index: HashMap<String, usize>,
+    blank_nodes: usize,
+    /// Prefixes declared by the document, mapped to their IRI.
+    prefixes: BTreeMap<String, String>,
+}
+
+impl Graph {
+    pub(crate) fn node(&self, id: &str) -> Option<&Node> {
+        self.index.get(id).map(|i| &self.nodes[*i])
+    }
+
+    pub(crate) fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter()
+    }
+
+    /// Returns the node with the given identifier, creating it if missing.
+    pub(crate) fn node_mut(&mut self, id: &str) -> &mut Node {
+        let i = match self.index.get(id) {
+            Some(i) => *i,
+            None => {
+                self.nodes.push(Node {
+                    id: id.to_owned(),
+                    ..Node::default()
+                });
+                self.index.insert(id.to_owned(), self.nodes.len() - 1);
+                self.nodes.len() - 1
+            }
+        };
+        &mut self.nodes[i]
+    }
+
+    /// Adds a statement to the graph.
+    pub(crate) fn add(&mut self, subject: &str, predicate: &str, object: Object) {
+        let node = self.node_mut(subject);
+        match object {
+            Object::Iri(iri) if predicate == RDF_TYPE => node.types.push(iri),
+            object => node
+                .properties
+                .entry(predicate.to_owned())
+                .or_default()
+                .push(object),
+        }
+    }
+
+    /// Records the prefixes declared by the document.
+    pub(crate) fn add_prefixes<'a>(&mut self, prefixes: impl Iterator<Item = (&'a str, &'a str)>) {
+        for (prefix, iri) in prefixes.filter(|(prefix, _)| !prefix.is_empty()) {
+            self.prefixes.insert(prefix.to_owned(), iri.to_owned());
+        }
+    }
+
+    /// Returns the first prefix, in alphabetical order, declared for an IRI.
+    pub(crate) fn prefix(&self, iri: &str) -> Option<&str> {
+        self.prefixes
+            .iter()
+            .find(|(_, prefix_iri)| *prefix_iri == iri)
+            .map(|(prefix, _)| prefix.as_str())
+    }
+
+    /// Returns a new blank node identifier.
+    pub(crate) fn blank_node(&mut self) -> String {
+        self.blank_nodes += 1;
+        format!("_:g{}", self.blank_nodes)
+    }
+
+    /// Replaces the RDF collections, encoded as chains of `rdf:first` and
+    /// `rdf:rest` statements, with lists.
+    pub(crate) fn fold_lists(&mut self) {
+        for i in 0..self.nodes.len() {
+            let properties = std::mem::take(&mut self.nodes[i].properties);
+            self.nodes[i].properties = properties
+                .into_iter()
+                .map(|(property, objects)| {
+                    let objects = objects
+                        .into_iter()
+                        .map(|object| match &object {
+                            Object::Iri(head)
+                                if head.starts_with("_:")
+                                    && property != RDF_FIRST
+                                    && property != RDF_REST =>
+                            {
+                                self.collection(head).map_or(object, Object::List)
+                            }
+                            _ => object,
+                        })
+                        .collect();
+                    (property, objects)
+                })
+                .collect();
+        }
+    }
+
+    // Returns the items of the collection starting at the given node.
+    fn collection(&self, head: &str) -> Option<Vec<Object>> {
+        let mut items = Vec::new();
+        let mut current = head;
+        while current != RDF_NIL {
+            let node = self.node(current)?;
+            match (node.values(RDF_FIRST), node.iris(RDF_REST).next()) {
+                ([first], Some(rest)) if items.len() < self.nodes.len() => {
+                    items.push(first.clone());
+                    current = rest;
+                }
+                _ => return None,
+            }
+        }
+        Some(items)
+    }
+}
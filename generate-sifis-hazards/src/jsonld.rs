@@ -0,0 +1,649 @@
+//! Minimal JSON-LD expansion.
+//!
+//! Expands a JSON-LD document, resolving terms, compact IRIs, relative IRIs
+//! and value coercions through its `@context`, into a graph of nodes keyed
+//! by absolute IRIs.
+//!
+//! Only the subset of JSON-LD needed by ontologies is supported: remote and
+//! scoped contexts, nested properties and the containers other than `@list`
+//! and `@set` are reported as errors, as are the properties not mapped to
+//! an IRI, so no statement is dropped silently.
+
+use std::collections::HashMap;
+
+use oxiri::Iri;
+use serde_json::{Map, Value};
+
+use crate::graph::{Graph, Object};
+use crate::ontology::OntologyError;
+
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+
+// Maximum depth of nested term definitions, to stop cyclic definitions.
+const MAX_TERM_DEPTH: usize = 16;
+
+impl Graph {
+    /// Expands a JSON-LD document.
+    pub(crate) fn from_json(document: &Value) -> Result<Self, OntologyError> {
+        let mut graph = Self::default();
+        graph.expand_top_level(&Context::default(), document)?;
+        Ok(graph)
+    }
+
+    fn expand_top_level(
+        &mut self,
+        context: &Context,
+        document: &Value,
+    ) -> Result<(), OntologyError> {
+        match document {
+            Value::Array(values) => values
+                .iter()
+                .try_for_each(|v| self.expand_top_level(context, v)),
+            Value::Object(object) => {
+                let context = match object.get("@context") {
+                    Some(local) => context.process(local).map_err(|message| OntologyError {
+                        node: node_label(object),
+                        message,
+                    })?,
+                    None => context.clone(),
+                };
+                self.add_prefixes(context.prefixes());
+                if let Some(graph) = object.get("@graph") {
+                    self.expand_top_level(&context, graph)?;
+                }
+                // An object with just a context and a graph is not a node
+                if object.keys().any(|k| k != "@context" && k != "@graph") {
+                    self.expand_node(&context, object)?;
+                }
+                Ok(())
+            }
+            _ => Err(OntologyError {
+                node: "<document>".to_owned(),
+                message: "expected a node object or an array of node objects".to_owned(),
+            }),
+        }
+    }
+
+    // Expands a node object and returns its identifier.
+    fn expand_node(
+        &mut self,
+        context: &Context,
+        object: &Map<String, Value>,
+    ) -> Result<String, OntologyError> {
+        let error = |message: String| OntologyError {
+            node: node_label(object),
+            message,
+        };
+
+        let context = match object.get("@context") {
+            Some(local) => context.process(local).map_err(error)?,
+            None => context.clone(),
+        };
+
+        let id = match object.get("@id") {
+            Some(Value::String(id)) => context.expand_iri(id, false).map_err(error)?,
+            Some(_) => return Err(error("`@id` must be a string".to_owned())),
+            None => self.blank_node(),
+        };
+        self.node_mut(&id);
+
+        for (key, value) in object {
+            match key.as_str() {
+                "@id" | "@context" | "@index" => {}
+                "@type" => {
+                    for t in one_or_many(value) {
+                        let t = t
+                            .as_str()
+                            .ok_or_else(|| error("`@type` must be a string".to_owned()))?;
+                        let t = context.expand_iri(t, true).map_err(error)?;
+                        self.node_mut(&id).types.push(t);
+                    }
+                }
+                "@graph" => self.expand_top_level(&context, value)?,
+                "@reverse" => {
+                    let properties = value
+                        .as_object()
+                        .ok_or_else(|| error("`@reverse` must be an object".to_owned()))?;
+                    for (key, value) in properties {
+                        self.expand_property(&context, &id, key, value, true)
+                            .map_err(error)?;
+                    }
+                }
+                keyword if keyword.starts_with('@') => {
+                    return Err(error(format!("unsupported keyword `{}`", keyword)))
+                }
+                _ => self
+                    .expand_property(&context, &id, key, value, false)
+                    .map_err(error)?,
+            }
+        }
+
+        Ok(id)
+    }
+
+    // Expands the values of a property of a node, adding the statements
+    // from the values to the node when the property is reversed.
+    fn expand_property(
+        &mut self,
+        context: &Context,
+        id: &str,
+        key: &str,
+        value: &Value,
+        reverse: bool,
+    ) -> Result<(), String> {
+        let property = context.expand_iri(key, true)?;
+        if property.starts_with('@') {
+            return Err(format!("keyword alias `{}` is not supported", key));
+        }
+        let term = context.terms.get(key);
+
+        let mut objects = Vec::new();
+        self.expand_value(context, term, value, &mut objects)
+            .map_err(|e| format!("`{}`: {}", key, e.message))?;
+        if term.is_some_and(|term| term.list) {
+            objects = vec![Object::List(objects)];
+        }
+
+        // A reverse property within `@reverse` is a regular one
+        if reverse != term.is_some_and(|term| term.reverse) {
+            for object in objects {
+                match object {
+                    Object::Iri(subject) => {
+                        self.add(&subject, &property, Object::Iri(id.to_owned()))
+                    }
+                    _ => return Err(format!("`{}`: reverse property values must be nodes", key)),
+                }
+            }
+        } else {
+            for object in objects {
+                self.add(id, &property, object);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expand_value(
+        &mut self,
+        context: &Context,
+        term: Option<&TermDefinition>,
+        value: &Value,
+        objects: &mut Vec<Object>,
+    ) -> Result<(), OntologyError> {
+        let error = |message: String| OntologyError {
+            node: String::new(),
+            message,
+        };
+        let type_mapping = term.and_then(|term| term.type_mapping.as_deref());
+        let datatype = |default: &str| -> Result<Option<String>, OntologyError> {
+            Ok(Some(match type_mapping {
+                Some(t) if t != "@id" && t != "@vocab" => {
+                    context.expand_iri(t, true).map_err(error)?
+                }
+                _ => default.to_owned(),
+            }))
+        };
+
+        match value {
+            Value::Null => {}
+            Value::Array(values) => {
+                for value in values {
+                    self.expand_value(context, term, value, objects)?;
+                }
+            }
+            Value::String(s) => objects.push(match type_mapping {
+                Some("@id") => Object::Iri(context.expand_iri(s, false).map_err(error)?),
+                Some("@vocab") => Object::Iri(context.expand_iri(s, true).map_err(error)?),
+                Some(t) => Object::Literal {
+                    value: s.to_owned(),
+                    lang: None,
+                    datatype: Some(context.expand_iri(t, true).map_err(error)?),
+                },
+                None => Object::Literal {
+                    value: s.to_owned(),
+                    lang: term
+                        .and_then(|term| term.language.clone())
+                        .or_else(|| context.language.clone()),
+                    datatype: None,
+                },
+            }),
+            Value::Number(n) => objects.push(Object::Literal {
+                value: n.to_string(),
+                lang: None,
+                datatype: datatype(if n.is_f64() { XSD_DOUBLE } else { XSD_INTEGER })?,
+            }),
+            Value::Bool(b) => objects.push(Object::Literal {
+                value: b.to_string(),
+                lang: None,
+                datatype: datatype(XSD_BOOLEAN)?,
+            }),
+            Value::Object(object) => {
+                if let Some(v) = object.get("@value") {
+                    if let Some(key) = object
+                        .keys()
+                        .find(|k| !["@value", "@type", "@language", "@index"].contains(&k.as_str()))
+                    {
+                        return Err(error(format!("unsupported `{}` in a value object", key)));
+                    }
+                    let value = match v {
+                        Value::String(s) => s.to_owned(),
+                        Value::Number(_) | Value::Bool(_) => v.to_string(),
+                        _ => return Err(error("`@value` must be a scalar".to_owned())),
+                    };
+                    let datatype = match object.get("@type") {
+                        Some(Value::String(t)) => Some(context.expand_iri(t, true).map_err(error)?),
+                        Some(_) => return Err(error("`@type` must be a string".to_owned())),
+                        None => None,
+                    };
+                    objects.push(Object::Literal {
+                        value,
+                        lang: object
+                            .get("@language")
+                            .and_then(|v| v.as_str())
+                            .map(str::to_owned),
+                        datatype,
+                    });
+                } else if let Some(list) = object.get("@list") {
+                    let mut items = Vec::new();
+                    self.expand_value(context, term, list, &mut items)?;
+                    objects.push(Object::List(items));
+                } else if let Some(set) = object.get("@set") {
+                    self.expand_value(context, term, set, objects)?;
+                } else if object.len() == 1 && object.contains_key("@id") {
+                    let id = object["@id"]
+                        .as_str()
+                        .ok_or_else(|| error("`@id` must be a string".to_owned()))?;
+                    objects.push(Object::Iri(context.expand_iri(id, false).map_err(error)?));
+                } else {
+                    let id = self.expand_node(context, object)?;
+                    objects.push(Object::Iri(id));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn one_or_many(value: &Value) -> &[Value] {
+    match value {
+        Value::Array(values) => values,
+        value => std::slice::from_ref(value),
+    }
+}
+
+fn node_label(object: &Map<String, Value>) -> String {
+    object
+        .get("@id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("<anonymous node>")
+        .to_owned()
+}
+
+#[derive(Clone, Debug, Default)]
+struct TermDefinition {
+    id: Option<String>,
+    type_mapping: Option<String>,
+    language: Option<String>,
+    list: bool,
+    reverse: bool,
+}
+
+impl TermDefinition {
+    fn parse(term: &str, definition: &Map<String, Value>) -> Result<Self, String> {
+        let string = |key: &str| match definition.get(key) {
+            Some(Value::String(value)) => Ok(Some(value.to_owned())),
+            Some(_) => Err(format!("`{}` of term `{}` must be a string", key, term)),
+            None => Ok(None),
+        };
+
+        let mut result = Self {
+            id: string("@id")?,
+            type_mapping: string("@type")?,
+            language: string("@language")?,
+            ..Self::default()
+        };
+        if let Some(id) = string("@reverse")? {
+            result.id = Some(id);
+            result.reverse = true;
+        }
+        match string("@container")?.as_deref() {
+            Some("@list") => result.list = true,
+            Some("@set") | None => {}
+            Some(container) => {
+                return Err(format!(
+                    "unsupported container `{}` of term `{}`",
+                    container, term
+                ))
+            }
+        }
+        if let Some(key) = definition.keys().find(|key| {
+            ![
+                "@id",
+                "@reverse",
+                "@type",
+                "@language",
+                "@container",
+                "@protected",
+                "@prefix",
+            ]
+            .contains(&key.as_str())
+        }) {
+            return Err(format!(
+                "unsupported `{}` in the definition of term `{}`",
+                key, term
+            ));
+        }
+
+        Ok(result)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Context {
+    terms: HashMap<String, TermDefinition>,
+    vocab: Option<String>,
+    base: Option<String>,
+    language: Option<String>,
+}
+
+impl Context {
+    // Returns the context resulting from the processing of a local context.
+    fn process(&self, local: &Value) -> Result<Self, String> {
+        match local {
+            Value::Null => Ok(Self::default()),
+            Value::Array(contexts) => contexts
+                .iter()
+                .try_fold(self.clone(), |context, local| context.process(local)),
+            Value::String(iri) => Err(format!("remote context `{}` is not supported", iri)),
+            Value::Object(definitions) => {
+                let mut context = self.clone();
+                for (key, value) in definitions {
+                    match key.as_str() {
+                        "@vocab" => context.vocab = value.as_str().map(str::to_owned),
+                        "@base" => {
+                            context.base = match value {
+                                Value::String(base) => Some(self.resolve(base)?),
+                                _ => None,
+                            }
+                        }
+                        "@language" => context.language = value.as_str().map(str::to_owned),
+                        "@version" | "@protected" => {}
+                        // Contexts always propagate to the nested node
+                        // objects, which is the default
+                        "@propagate" if value == &Value::Bool(true) => {}
+                        "@propagate" => {
+                            return Err("only `\"@propagate\": true` is supported".to_owned())
+                        }
+                        keyword if keyword.starts_with('@') => {
+                            return Err(format!("unsupported keyword `{}` in `@context`", keyword))
+                        }
+                        _ => {
+                            let definition = match value {
+                                Value::Null => {
+                                    context.terms.remove(key);
+                                    continue;
+                                }
+                                Value::String(id) => TermDefinition {
+                                    id: Some(id.to_owned()),
+                                    ..TermDefinition::default()
+                                },
+                                Value::Object(definition) => {
+                                    TermDefinition::parse(key, definition)?
+                                }
+                                _ => return Err(format!("invalid definition for term `{}`", key)),
+                            };
+                            context.terms.insert(key.to_owned(), definition);
+                        }
+                    }
+                }
+                Ok(context)
+            }
+            _ => Err("invalid `@context`".to_owned()),
+        }
+    }
+
+    // Returns the terms which can be used as prefixes, those mapped to an
+    // IRI ending with a `/` or a `#`.
+    fn prefixes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.terms.iter().filter_map(|(term, definition)| {
+            let id = definition.id.as_deref()?;
+            (id.ends_with('/') || id.ends_with('#')).then_some((term.as_str(), id))
+        })
+    }
+
+    // Resolves a relative IRI against the base IRI, as defined by RFC 3986.
+    fn resolve(&self, value: &str) -> Result<String, String> {
+        if let Ok(iri) = Iri::parse(value) {
+            return Ok(iri.into_inner().to_owned());
+        }
+        let base = self
+            .base
+            .as_deref()
+            .ok_or_else(|| format!("relative IRI `{}` without a base IRI", value))?;
+        Iri::parse(base)
+            .and_then(|base| base.resolve(value))
+            .map(Iri::into_inner)
+            .map_err(|e| format!("cannot resolve `{}` against `{}`: {}", value, base, e))
+    }
+
+    // Expands a term, a compact IRI or a relative IRI into an absolute IRI.
+    //
+    // `vocab` is set for property names, types and values coerced
+    // through `@vocab`, which are resolved against the vocabulary mapping
+    // rather than against the base IRI.
+    fn expand_iri(&self, value: &str, vocab: bool) -> Result<String, String> {
+        self.expand_iri_depth(value, vocab, 0)
+    }
+
+    fn expand_iri_depth(&self, value: &str, vocab: bool, depth: usize) -> Result<String, String> {
+        if value.starts_with('@') {
+            return Ok(value.to_owned());
+        }
+        if depth > MAX_TERM_DEPTH {
+            return Err(format!("cyclic definition of `{}`", value));
+        }
+
+        if vocab {
+            if let Some(id) = self.terms.get(value).and_then(|term| term.id.as_deref()) {
+                if id != value {
+                    return self.expand_iri_depth(id, true, depth + 1);
+                }
+            }
+        }
+
+        if let Some((prefix, suffix)) = value.split_once(':') {
+            if prefix == "_" || suffix.starts_with("//") {
+                return Ok(value.to_owned());
+            }
+            if let Some(id) = self.terms.get(prefix).and_then(|term| term.id.as_deref()) {
+                return Ok(format!(
+                    "{}{}",
+                    self.expand_iri_depth(id, true, depth + 1)?,
+                    suffix
+                ));
+            }
+            return Ok(value.to_owned());
+        }
+
+        match &self.vocab {
+            Some(vocab_iri) if vocab => Ok(format!("{}{}", vocab_iri, value)),
+            None if vocab => Err(format!("`{}` is not mapped to an IRI", value)),
+            _ => self.resolve(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SHO: &str = "https://purl.org/sifis/hazards#";
+
+    fn assert_fire_hazard(graph: &Graph) {
+        let node = graph.node(&format!("{}FireHazard", SHO)).unwrap();
+        assert!(node.has_type(&format!("{}Hazard", SHO)));
+        assert_eq!(
+            node.iris(&format!("{}hasCategory", SHO))
+                .collect::<Vec<_>>(),
+            [format!("{}Safety", SHO)]
+        );
+        assert_eq!(
+            node.literals("http://www.w3.org/2000/01/rdf-schema#label")
+                .collect::<Vec<_>>(),
+            [("FireHazard", Some("en"))]
+        );
+    }
+
+    #[test]
+    fn compacted() {
+        let document = serde_json::json!({
+            "@context": {
+                "haz": SHO,
+                "rdfs": "http://www.w3.org/2000/01/rdf-schema#",
+                "label": "rdfs:label",
+                "hasCategory": { "@id": "haz:hasCategory", "@type": "@id" }
+            },
+            "@graph": [{
+                "@id": "haz:FireHazard",
+                "@type": "haz:Hazard",
+                "label": { "@value": "FireHazard", "@language": "en" },
+                "hasCategory": "haz:Safety"
+            }]
+        });
+
+        assert_fire_hazard(&Graph::from_json(&document).unwrap());
+    }
+
+    #[test]
+    fn expanded() {
+        let document = serde_json::json!([{
+            "@id": format!("{}FireHazard", SHO),
+            "http://www.w3.org/1999/02/22-rdf-syntax-ns#type": [{ "@id": format!("{}Hazard", SHO) }],
+            "http://www.w3.org/2000/01/rdf-schema#label": [
+                { "@value": "FireHazard", "@language": "en" }
+            ],
+            format!("{}hasCategory", SHO): [{ "@id": format!("{}Safety", SHO) }]
+        }]);
+
+        assert_fire_hazard(&Graph::from_json(&document).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let document = serde_json::json!({
+            "@context": "https://example.com/context.jsonld",
+            "@id": "sho:FireHazard"
+        });
+        let err = Graph::from_json(&document).unwrap_err();
+        assert_eq!(err.node, "sho:FireHazard");
+
+        let document = serde_json::json!([{ "@id": "sho:FireHazard", "@type": 3 }]);
+        assert!(Graph::from_json(&document).is_err());
+
+        let error = |node: serde_json::Value| {
+            let document = serde_json::json!({
+                "@context": { "sho": SHO },
+                "@graph": [node]
+            });
+            Graph::from_json(&document).unwrap_err()
+        };
+
+        let err = error(serde_json::json!({ "@id": "sho:FireHazard", "description": "Fire" }));
+        assert_eq!(err.node, "sho:FireHazard");
+        assert_eq!(err.message, "`description` is not mapped to an IRI");
+
+        let err = error(serde_json::json!({ "@id": "sho:FireHazard", "@nest": {} }));
+        assert_eq!(err.message, "unsupported keyword `@nest`");
+
+        let err = error(serde_json::json!({ "@id": "FireHazard" }));
+        assert_eq!(err.message, "relative IRI `FireHazard` without a base IRI");
+
+        let document = serde_json::json!({
+            "@context": { "label": { "@id": "rdfs:label", "@container": "@language" } },
+            "@id": "sho:FireHazard"
+        });
+        let err = Graph::from_json(&document).unwrap_err();
+        assert_eq!(err.node, "sho:FireHazard");
+        assert_eq!(
+            err.message,
+            "unsupported container `@language` of term `label`"
+        );
+
+        let document = serde_json::json!({
+            "@context": { "@propagate": false, "sho": SHO },
+            "@id": "sho:FireHazard"
+        });
+        let err = Graph::from_json(&document).unwrap_err();
+        assert_eq!(err.message, "only `\"@propagate\": true` is supported");
+
+        let document = serde_json::json!({
+            "@context": { "@propagate": true, "sho": SHO },
+            "@id": "sho:FireHazard"
+        });
+        assert!(Graph::from_json(&document).is_ok());
+    }
+
+    #[test]
+    fn reverse() {
+        let document = serde_json::json!({
+            "@context": {
+                "haz": SHO,
+                "rdfs": "http://www.w3.org/2000/01/rdf-schema#",
+                "hazards": { "@reverse": "haz:hasCategory", "@type": "@id" }
+            },
+            "@graph": [{
+                "@id": "haz:Safety",
+                "hazards": ["haz:FireHazard"]
+            }, {
+                "@id": "haz:Hazard",
+                "@reverse": {
+                    "http://www.w3.org/1999/02/22-rdf-syntax-ns#type": { "@id": "haz:FireHazard" }
+                }
+            }, {
+                "@id": "haz:FireHazard",
+                "rdfs:label": { "@value": "FireHazard", "@language": "en" }
+            }]
+        });
+
+        assert_fire_hazard(&Graph::from_json(&document).unwrap());
+
+        let document = serde_json::json!({
+            "@id": "haz:Safety",
+            "@reverse": { "http://example.com/p": "literal" }
+        });
+        let err = Graph::from_json(&document).unwrap_err();
+        assert_eq!(
+            err.message,
+            "`http://example.com/p`: reverse property values must be nodes"
+        );
+    }
+
+    #[test]
+    fn relative_iris() {
+        let document = serde_json::json!({
+            "@context": {
+                "@base": "https://purl.org/sifis/hazards/v1/ontology",
+                "rdfs": "http://www.w3.org/2000/01/rdf-schema#",
+                "seeAlso": { "@id": "rdfs:seeAlso", "@type": "@id" }
+            },
+            "@id": "#FireHazard",
+            "seeAlso": ["../docs/fire?lang=en", "other"]
+        });
+
+        let graph = Graph::from_json(&document).unwrap();
+        let node = graph
+            .node("https://purl.org/sifis/hazards/v1/ontology#FireHazard")
+            .unwrap();
+        assert_eq!(
+            node.iris("http://www.w3.org/2000/01/rdf-schema#seeAlso")
+                .collect::<Vec<_>>(),
+            [
+                "https://purl.org/sifis/hazards/docs/fire?lang=en",
+                "https://purl.org/sifis/hazards/v1/other"
+            ]
+        );
+    }
+}
@@ -1,27 +1,58 @@
+mod check;
+mod diff;
 mod filters;
+mod generator;
+mod graph;
+mod jsonld;
+mod lockfile;
+mod ontology;
+mod rdf;
+mod template_dir;
 mod toolchain;
 
-use std::collections::HashMap;
-use std::fs::{create_dir_all, write, File};
-use std::io::BufReader;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use arg_enum_proc_macro::ArgEnum;
 use minijinja::value::Value;
 use minijinja::{Environment, Source};
-use serde::Deserialize;
 use tracing::debug;
 
 use filters::*;
+use ontology::Ontology;
 use toolchain::*;
 
+pub use check::{check_ontology, CheckReport, Problem, Severity};
+pub use diff::{diff_ontologies, Change, DiffReport, Item, SemverBump};
+pub use generator::{Generator, StaleFiles};
+pub use ontology::OntologyError;
+pub use rdf::Format;
+
 /// Supported templates
-#[derive(ArgEnum, Debug)]
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Templates {
     /// Generate hazards documentation for Rust APIs
     #[arg_enum(name = "rust")]
     Rust,
+    /// Generate a C header describing the hazards
+    #[arg_enum(name = "c")]
+    C,
+    /// Generate a Python package describing the hazards
+    #[arg_enum(name = "python")]
+    Python,
+    /// Generate a TypeScript module describing the hazards
+    #[arg_enum(name = "typescript")]
+    TypeScript,
+    /// Generate a Markdown catalogue of the hazards
+    #[arg_enum(name = "markdown")]
+    Markdown,
+    /// Generate an HTML catalogue of the hazards
+    #[arg_enum(name = "html")]
+    Html,
+    /// Generate a JSON Schema and an OpenAPI fragment of the hazards
+    #[arg_enum(name = "json-schema")]
+    JsonSchema,
 }
 
 impl Templates {
@@ -37,77 +68,121 @@ impl Templates {
 
         info
     }
-}
 
-#[derive(Deserialize)]
-struct Ontology {
-    #[serde(rename = "@graph")]
-    graph: Vec<serde_json::Value>,
-    #[serde(skip_deserializing)]
-    _context: serde_json::Value,
+    /// Returns all the built-in templates.
+    pub fn all() -> Vec<Self> {
+        Templates::variants()
+            .iter()
+            .filter_map(|name| name.parse().ok())
+            .collect()
+    }
+
+    /// Returns whether a name can be used as an identifier in the code
+    /// generated by the template.
+    pub fn is_valid_identifier(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let is_identifier = name != "_"
+            && chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        is_identifier
+            && !match self {
+                Templates::Rust => Rust::is_keyword(name),
+                Templates::C => C::is_keyword(name),
+                Templates::Python => Python::is_keyword(name),
+                Templates::TypeScript => TypeScript::is_keyword(name),
+                Templates::Markdown => Markdown::is_keyword(name),
+                Templates::Html => Html::is_keyword(name),
+                Templates::JsonSchema => JsonSchema::is_keyword(name),
+            }
+    }
 }
 
 struct SifisTemplate {
     context: HashMap<String, Value>,
-    files: HashMap<PathBuf, &'static str>,
-    dirs: Vec<PathBuf>,
+    files: HashMap<PathBuf, String>,
     source: Source,
+    format: fn(&Path, String) -> Result<String>,
 }
 
 impl SifisTemplate {
-    fn render(self) -> Result<()> {
+    // Renders the files, keyed by their path relative to the output directory.
+    fn render(self) -> Result<BTreeMap<PathBuf, String>> {
         let mut env = Environment::new();
         let SifisTemplate {
             context,
             files,
-            dirs,
             source,
+            format,
         } = self;
 
-        // Create dirs
-        for dir in dirs {
-            debug!("Creating {}", dir.display());
-            create_dir_all(dir)?
-        }
-
         env.set_source(source);
         env.add_filter("hypens_to_underscores", hypens_to_underscores);
+        env.add_filter("snake_case", snake_case);
+        env.add_filter("screaming_snake_case", screaming_snake_case);
+        env.add_filter("kebab_case", kebab_case);
+        env.add_filter("camel_case", camel_case);
+        env.add_filter("pascal_case", pascal_case);
+        env.add_filter("escape_rust", escape_rust);
+        env.add_filter("escape_c", escape_c);
+        env.add_filter("escape_json", escape_json);
+        env.add_filter("escape_python", escape_python);
+        env.add_filter("escape_markdown", escape_markdown);
+        env.add_filter("fold_lines", fold_lines);
+        env.add_filter("wrap_comment", wrap_comment);
+        env.add_filter("comment", comment);
+        env.add_filter("iri_local_name", iri_local_name);
 
         // Fill in templates
+        let mut rendered = BTreeMap::new();
         for (path, template_name) in files {
-            debug!("Creating {}", path.display());
-            let template = env.get_template(template_name)?;
-            let filled_template = template.render(&context)?;
-            write(path, filled_template)?;
+            debug!("Rendering {}", path.display());
+            let template = env.get_template(&template_name)?;
+            let mut content = format(&path, template.render(&context)?)?;
+            // minijinja drops the trailing newline of the templates
+            if !content.ends_with('\n') {
+                content.push('\n');
+            }
+            rendered.insert(path, content);
         }
 
-        Ok(())
+        Ok(rendered)
     }
 }
 
 /// Build a template
 trait BuildTemplate {
+    /// Returns the templates to render, keyed by the relative path of the
+    /// generated files, and their context.
     fn define(
         &self,
-        ontology: Ontology,
-        output_path: &Path,
-    ) -> (
-        HashMap<PathBuf, &'static str>,
-        Vec<PathBuf>,
-        HashMap<String, Value>,
-    );
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>);
 
     fn get_templates() -> &'static [(&'static str, &'static str)];
 
-    fn build(&self, ontology: Ontology, output_path: &Path) -> SifisTemplate {
-        let (files, dirs, context) = self.define(ontology, output_path);
+    /// Returns whether a name is reserved in the target language.
+    fn is_keyword(name: &str) -> bool;
+
+    /// Formats a rendered file, given its relative path.
+    fn format(_path: &Path, content: String) -> Result<String> {
+        Ok(content)
+    }
+
+    fn build(&self, ontology: &Ontology) -> SifisTemplate {
+        let (files, context) = self.define(ontology);
         let source = build_source(Self::get_templates());
 
         SifisTemplate {
             context,
-            files,
-            dirs,
+            files: files
+                .into_iter()
+                .map(|(path, template)| (path, template.to_owned()))
+                .collect(),
             source,
+            format: Self::format,
         }
     }
 }
@@ -124,21 +199,13 @@ fn build_source(templates: &[(&str, &str)]) -> Source {
 }
 
 /// Adds hazards to Sifis APIs
+///
+/// The ontology format is detected from the file extension when not given.
 pub fn adds_hazards_to_api(
     template_type: Templates,
     ontology_path: &Path,
+    format: Option<Format>,
     output_path: &Path,
 ) -> Result<()> {
-    // Deserialize ontology
-    let file = File::open(ontology_path)?;
-    let reader = BufReader::new(file);
-
-    // Read the JSON contents of the file as an instance of `User`.
-    let ontology = serde_json::from_reader(reader)?;
-
-    let template = match template_type {
-        Templates::Rust => Rust::create().build(ontology, output_path),
-    };
-
-    template.render()
+    Generator::open(template_type, ontology_path, format)?.write(output_path)
 }
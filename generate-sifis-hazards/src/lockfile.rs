@@ -0,0 +1,298 @@
+//! Stable bit positions of the hazards.
+//!
+//! A `HazardSet` stores each hazard as a bit of a `u64`. The positions are
+//! recorded in a lockfile, so adding or removing hazards from the ontology
+//! does not move the bits of the other ones, which would corrupt the sets
+//! already stored.
+//!
+//! Hazards removed from the ontology keep their entry, so their bits are
+//! never given to new hazards.
+//!
+//! Hazards are identified by name, so a renamed hazard looks like a removed
+//! hazard and a new one, and gets a new bit. Recording the old name in the
+//! `[renamed]` table moves its bit to the new name instead:
+//!
+//! ```toml
+//! [renamed]
+//! Fire = "FireHazard"
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::ontology::HazardData;
+
+/// Number of bits of a `HazardSet`.
+const BITS: u8 = 64;
+
+const HEADER: &str = "# Bit positions of the hazards in `HazardSet`, generated by\n\
+                      # generate-sifis-hazards. Commit this file and only edit the\n\
+                      # `[renamed]` table, mapping old hazard names to new ones.\n\n";
+
+/// Bit positions of the hazards
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Lockfile {
+    /// Bit positions, keyed by hazard name.
+    pub(crate) hazards: BTreeMap<String, u8>,
+    /// New names of the renamed hazards, keyed by their old name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) renamed: BTreeMap<String, String>,
+}
+
+impl Lockfile {
+    /// Reads a lockfile, returning an empty one if it does not exist.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let lockfile = match fs::read_to_string(path) {
+            Ok(lockfile) => lockfile,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).context(format!("Cannot read {}", path.display())),
+        };
+
+        let lockfile: Self = toml::from_str(&lockfile)
+            .with_context(|| format!("Invalid lockfile {}", path.display()))?;
+        lockfile
+            .validate()
+            .with_context(|| format!("Invalid lockfile {}", path.display()))?;
+
+        Ok(lockfile)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut names = BTreeMap::new();
+        for (name, &bit) in &self.hazards {
+            if bit >= BITS {
+                bail!(
+                    "bit {} of `{}` out of the range [0, {}]",
+                    bit,
+                    name,
+                    BITS - 1
+                );
+            }
+            if let Some(other) = names.insert(bit, name) {
+                bail!("bit {} assigned to both `{}` and `{}`", bit, other, name);
+            }
+        }
+        if let Some((old, _)) = self.renamed.iter().find(|(old, new)| old == new) {
+            bail!("`{}` renamed to itself", old);
+        }
+
+        Ok(())
+    }
+
+    // Returns the hazard of the ontology a hazard has been renamed to,
+    // following the successive renames.
+    fn renamed_to(&self, old: &str, names: &BTreeSet<&str>) -> Option<String> {
+        let mut name = old;
+        for _ in 0..self.renamed.len() {
+            name = self.renamed.get(name)?;
+            if names.contains(name) {
+                return Some(name.to_owned());
+            }
+        }
+        None
+    }
+
+    /// Moves the bits of the renamed hazards to their new name, then assigns
+    /// the next free bits to the hazards without one, in order.
+    ///
+    /// Returns the names of the hazards which got a new bit.
+    pub(crate) fn update(&mut self, hazards: &[HazardData]) -> Result<Vec<String>> {
+        let names: BTreeSet<_> = hazards.iter().map(|hazard| hazard.name.as_str()).collect();
+        let removed: Vec<_> = self
+            .hazards
+            .keys()
+            .filter(|name| !names.contains(name.as_str()))
+            .cloned()
+            .collect();
+        for old in removed {
+            let new = match self.renamed_to(&old, &names) {
+                Some(new) => new,
+                None => continue,
+            };
+            let bit = self.hazards[&old];
+            if let Some(other) = self.hazards.get(&new) {
+                bail!(
+                    "`{}` renamed to `{}`, which already has bit {}: remove `{}` from \
+                     the `[hazards]` table to keep bit {}",
+                    old,
+                    new,
+                    other,
+                    new,
+                    bit
+                );
+            }
+            self.hazards.remove(&old);
+            self.hazards.insert(new, bit);
+        }
+
+        let mut added = Vec::new();
+        let mut next = self.hazards.values().map(|bit| bit + 1).max().unwrap_or(0);
+        for hazard in hazards {
+            if self.hazards.contains_key(&hazard.name) {
+                continue;
+            }
+            if next >= BITS {
+                bail!(
+                    "No bit left for hazard `{}`, a `HazardSet` holds at most {} hazards",
+                    hazard.name,
+                    BITS
+                );
+            }
+            self.hazards.insert(hazard.name.clone(), next);
+            added.push(hazard.name.clone());
+            next += 1;
+        }
+
+        Ok(added)
+    }
+
+    /// Returns the hazards with a bit which are not in the ontology and have
+    /// not been renamed.
+    pub(crate) fn removed<'a>(&'a self, hazards: &[HazardData]) -> Vec<&'a str> {
+        self.hazards
+            .keys()
+            .filter(|name| !hazards.iter().any(|hazard| &&hazard.name == name))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Returns the content of the lockfile.
+    pub(crate) fn render(&self) -> String {
+        let table = toml::to_string(self).expect("Internal error, serializable lockfile");
+        format!("{}{}", HEADER, table)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hazards(names: &[&str]) -> Vec<HazardData> {
+        names
+            .iter()
+            .map(|name| HazardData {
+                iri: String::new(),
+                has_risk_score: false,
+                default_risk_score: None,
+                description: String::new(),
+                descriptions: Vec::new(),
+                labels: Vec::new(),
+                comments: Vec::new(),
+                name: (*name).to_owned(),
+                category: String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stable_bits() {
+        let mut lockfile = Lockfile::default();
+        lockfile
+            .update(&hazards(&["Fire", "Flood", "Smoke"]))
+            .unwrap();
+
+        let mut updated = lockfile.clone();
+        let added = updated
+            .update(&hazards(&["Explosion", "Fire", "Smoke"]))
+            .unwrap();
+        assert_eq!(added, ["Explosion"]);
+        assert_eq!(
+            updated.removed(&hazards(&["Explosion", "Fire", "Smoke"])),
+            ["Flood"]
+        );
+        assert_eq!(
+            updated.hazards.into_iter().collect::<Vec<_>>(),
+            [
+                ("Explosion".to_owned(), 3),
+                ("Fire".to_owned(), 0),
+                ("Flood".to_owned(), 1),
+                ("Smoke".to_owned(), 2),
+            ]
+        );
+
+        let rendered = lockfile.render();
+        assert!(rendered.ends_with("[hazards]\nFire = 0\nFlood = 1\nSmoke = 2\n"));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ontology.lock");
+        assert_eq!(Lockfile::open(&path).unwrap(), Lockfile::default());
+        fs::write(&path, rendered).unwrap();
+        assert_eq!(Lockfile::open(&path).unwrap(), lockfile);
+    }
+
+    #[test]
+    fn renames() {
+        let mut lockfile = Lockfile::default();
+        lockfile.update(&hazards(&["Fire", "Flood"])).unwrap();
+        lockfile
+            .renamed
+            .insert("Fire".to_owned(), "FireHazard".to_owned());
+
+        let renamed = hazards(&["FireHazard", "Flood", "Smoke"]);
+        assert_eq!(lockfile.update(&renamed).unwrap(), ["Smoke"]);
+        assert!(lockfile.removed(&renamed).is_empty());
+        assert!(lockfile.render().ends_with(
+            "[hazards]\nFireHazard = 0\nFlood = 1\nSmoke = 2\n\n[renamed]\nFire = \"FireHazard\"\n"
+        ));
+        let mut updated = lockfile.clone();
+        assert!(updated.update(&renamed).unwrap().is_empty());
+        assert_eq!(updated, lockfile);
+
+        // Successive renames, and a new hazard reusing an old name
+        lockfile
+            .renamed
+            .insert("FireHazard".to_owned(), "Fires".to_owned());
+        let added = lockfile
+            .update(&hazards(&["Fire", "Fires", "Flood", "Smoke"]))
+            .unwrap();
+        assert_eq!(added, ["Fire"]);
+        assert_eq!(lockfile.hazards["Fires"], 0);
+        assert_eq!(lockfile.hazards["Fire"], 3);
+
+        // The rename is recorded after the new name got a bit
+        let mut lockfile = Lockfile::default();
+        lockfile.update(&hazards(&["Fire", "FireHazard"])).unwrap();
+        lockfile
+            .renamed
+            .insert("Fire".to_owned(), "FireHazard".to_owned());
+        let err = lockfile.update(&hazards(&["FireHazard"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Fire` renamed to `FireHazard`, which already has bit 1: remove `FireHazard` \
+             from the `[hazards]` table to keep bit 0"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let mut lockfile = Lockfile {
+            hazards: [("Fire".to_owned(), 63)].into(),
+            ..Lockfile::default()
+        };
+        let err = lockfile.update(&hazards(&["Flood"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No bit left for hazard `Flood`, a `HazardSet` holds at most 64 hazards"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ontology.lock");
+        let error = |content| {
+            fs::write(&path, content).unwrap();
+            format!("{:#}", Lockfile::open(&path).unwrap_err())
+        };
+        assert!(
+            error("[hazards]\nFire = 64\n").ends_with("bit 64 of `Fire` out of the range [0, 63]")
+        );
+        assert!(error("[hazards]\nFire = 1\nFlood = 1\n")
+            .ends_with("bit 1 assigned to both `Fire` and `Flood`"));
+        assert!(error("[hazards]\nFire = 1\n\n[renamed]\nFire = \"Fire\"\n")
+            .ends_with("`Fire` renamed to itself"));
+        assert!(error("[hazards]\nFire = -1\n").contains("Invalid lockfile"));
+    }
+}
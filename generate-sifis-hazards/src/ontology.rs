@@ -0,0 +1,406 @@
+//! Ontology model shared by all the templates.
+//!
+//! The model is built from the expanded graph of the ontology, so it does
+//! not depend on the prefixes or on the form of the serialization.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use minijinja::value::Value;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::graph::{Graph, Node, Object};
+use crate::rdf::Format;
+
+/// Namespace of the SIFIS hazards ontology.
+pub(crate) const SHO: &str = "https://purl.org/sifis/hazards#";
+const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+const OWL: &str = "http://www.w3.org/2002/07/owl#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+const DCTERMS: &str = "http://purl.org/dc/terms/";
+const VANN: &str = "http://purl.org/vocab/vann/";
+
+/// Error found while reading an ontology.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OntologyError {
+    /// Identifier of the offending node.
+    pub node: String,
+    pub message: String,
+}
+
+impl fmt::Display for OntologyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid ontology node `{}`: {}", self.node, self.message)
+    }
+}
+
+impl std::error::Error for OntologyError {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct LocalizedText {
+    pub(crate) lang: String,
+    pub(crate) value: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct OntologyData {
+    pub(crate) iri: String,
+    pub(crate) version: String,
+    pub(crate) modified: String,
+    pub(crate) title: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct RiskScoreRange {
+    pub(crate) min: i64,
+    pub(crate) max: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct HazardData {
+    pub(crate) iri: String,
+    pub(crate) has_risk_score: bool,
+    pub(crate) default_risk_score: Option<i64>,
+    pub(crate) description: String,
+    pub(crate) descriptions: Vec<LocalizedText>,
+    pub(crate) labels: Vec<LocalizedText>,
+    pub(crate) comments: Vec<LocalizedText>,
+    pub(crate) name: String,
+    pub(crate) category: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct CategoryData {
+    pub(crate) iri: String,
+    pub(crate) description: String,
+    pub(crate) descriptions: Vec<LocalizedText>,
+    pub(crate) labels: Vec<LocalizedText>,
+    pub(crate) comments: Vec<LocalizedText>,
+    pub(crate) name: String,
+    pub(crate) hazards: Vec<String>,
+}
+
+/// SIFIS hazards ontology.
+#[derive(Clone, Debug)]
+pub(crate) struct Ontology {
+    pub(crate) namespace: String,
+    /// Prefix of the namespace in compact IRIs.
+    pub(crate) prefix: Option<String>,
+    pub(crate) metadata: OntologyData,
+    pub(crate) risk_score: RiskScoreRange,
+    /// Hazards, sorted by name.
+    pub(crate) hazards: Vec<HazardData>,
+    /// Categories, sorted by name.
+    pub(crate) categories: Vec<CategoryData>,
+}
+
+pub(crate) fn sho(name: &str) -> String {
+    format!("{}{}", SHO, name)
+}
+
+// Returns the name of an individual defined in the SIFIS namespace.
+pub(crate) fn local_name(node: &str) -> Result<&str, OntologyError> {
+    node.strip_prefix(SHO)
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| OntologyError {
+            node: node.to_owned(),
+            message: format!("not defined in the `{}` namespace", SHO),
+        })
+}
+
+fn localized_texts(node: &Node, property: &str) -> Vec<LocalizedText> {
+    node.literals(property)
+        .map(|(value, lang)| LocalizedText {
+            lang: lang.unwrap_or_default().to_owned(),
+            value: value.to_owned(),
+        })
+        .collect()
+}
+
+fn first_text(node: &Node, property: &str) -> String {
+    node.literals(property)
+        .next()
+        .map(|(value, _)| value.to_owned())
+        .unwrap_or_default()
+}
+
+fn first_integer(node: &Node, property: &str) -> Option<i64> {
+    node.literals(property)
+        .find_map(|(value, _)| value.parse().ok())
+}
+
+// Reads the range of the risk score from the restrictions of the datatype
+// equivalent to `sho:level`.
+pub(crate) fn risk_score_range(graph: &Graph) -> Option<RiskScoreRange> {
+    let level = graph.node(&sho("level"))?;
+    let datatype = graph.node(level.iris(&format!("{}equivalentClass", OWL)).next()?)?;
+    let restrictions = datatype
+        .values(&format!("{}withRestrictions", OWL))
+        .iter()
+        .find_map(|object| match object {
+            Object::List(items) => Some(items),
+            _ => None,
+        })?;
+
+    let (mut min, mut max) = (None, None);
+    for restriction in restrictions.iter().filter_map(Object::as_iri) {
+        let restriction = graph.node(restriction)?;
+        min = min.or_else(|| first_integer(restriction, &format!("{}minInclusive", XSD)));
+        max = max.or_else(|| first_integer(restriction, &format!("{}maxInclusive", XSD)));
+    }
+    Some(RiskScoreRange {
+        min: min?,
+        max: max?,
+    })
+}
+
+impl Ontology {
+    /// Reads the ontology from a file.
+    pub(crate) fn open(path: &Path, format: Option<Format>) -> anyhow::Result<Self> {
+        Ok(Self::from_graph(&Graph::open(path, format)?)?)
+    }
+
+    /// Reads the ontology from its expanded graph.
+    pub(crate) fn from_graph(graph: &Graph) -> Result<Self, OntologyError> {
+        let ontology = graph
+            .nodes()
+            .find(|node| node.has_type(&format!("{}Ontology", OWL)));
+        let metadata = ontology
+            .map(|node| OntologyData {
+                iri: node.id.clone(),
+                version: first_text(node, &format!("{}versionInfo", OWL)),
+                modified: first_text(node, &format!("{}modified", DCTERMS)),
+                title: first_text(node, &format!("{}title", DCTERMS)),
+            })
+            .unwrap_or_else(|| {
+                warn!("Ontology metadata not found");
+                OntologyData::default()
+            });
+        let risk_score = risk_score_range(graph).unwrap_or_else(|| {
+            warn!("Risk score range not found, using the default one");
+            RiskScoreRange { min: 0, max: 10 }
+        });
+        // Risk scores are stored as `u8` by the generated APIs
+        if risk_score.min < 0 || risk_score.max > u8::MAX.into() || risk_score.min > risk_score.max
+        {
+            return Err(OntologyError {
+                node: sho("level"),
+                message: format!(
+                    "invalid risk score range {}..={}",
+                    risk_score.min, risk_score.max
+                ),
+            });
+        }
+        // The prefix declared by the ontology takes precedence over the one
+        // the document happens to use
+        let prefix = ontology
+            .map(|node| first_text(node, &format!("{}preferredNamespacePrefix", VANN)))
+            .filter(|prefix| !prefix.is_empty())
+            .or_else(|| graph.prefix(SHO).map(str::to_owned));
+        if prefix.is_none() {
+            warn!("No prefix declared for `{}`", SHO);
+        }
+
+        let description = |node: &Node| {
+            let descriptions = localized_texts(node, &sho("description"));
+            if descriptions.is_empty() {
+                warn!("`{}` has no description", node.id);
+            }
+            descriptions
+        };
+
+        let mut categories = Vec::new();
+        for node in graph.nodes().filter(|node| node.has_type(&sho("Category"))) {
+            let descriptions = description(node);
+            categories.push(CategoryData {
+                iri: node.id.clone(),
+                description: descriptions
+                    .first()
+                    .map(|text| text.value.clone())
+                    .unwrap_or_default(),
+                descriptions,
+                labels: localized_texts(node, &format!("{}label", RDFS)),
+                comments: localized_texts(node, &format!("{}comment", RDFS)),
+                name: local_name(&node.id)?.to_owned(),
+                hazards: Vec::new(),
+            });
+        }
+        categories.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut hazards = Vec::new();
+        for node in graph.nodes().filter(|node| node.has_type(&sho("Hazard"))) {
+            let error = |message: &str| OntologyError {
+                node: node.id.clone(),
+                message: message.to_owned(),
+            };
+
+            let category = match node.values(&sho("hasCategory")) {
+                [Object::Iri(category)] => category,
+                [] => return Err(error("missing `hasCategory`")),
+                [_] => return Err(error("`hasCategory` must reference a category")),
+                _ => return Err(error("more than one `hasCategory`")),
+            };
+            let category = categories
+                .iter()
+                .find(|data| &data.iri == category)
+                .ok_or_else(|| error(&format!("undefined category `{}`", category)))?;
+
+            let default_risk_score = first_integer(node, &sho("riskScore"));
+            if let Some(score) = default_risk_score {
+                if !(risk_score.min..=risk_score.max).contains(&score) {
+                    return Err(error(&format!(
+                        "risk score {} outside {}..={}",
+                        score, risk_score.min, risk_score.max
+                    )));
+                }
+            }
+            let descriptions = description(node);
+            hazards.push(HazardData {
+                iri: node.id.clone(),
+                has_risk_score: !node.values(&sho("riskScore")).is_empty(),
+                default_risk_score,
+                description: descriptions
+                    .first()
+                    .map(|text| text.value.clone())
+                    .unwrap_or_default(),
+                descriptions,
+                labels: localized_texts(node, &format!("{}label", RDFS)),
+                comments: localized_texts(node, &format!("{}comment", RDFS)),
+                name: local_name(&node.id)?.to_owned(),
+                category: category.name.clone(),
+            });
+        }
+        hazards.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for category in &mut categories {
+            category.hazards = hazards
+                .iter()
+                .filter(|hazard| hazard.category == category.name)
+                .map(|hazard| hazard.name.clone())
+                .collect();
+        }
+
+        Ok(Self {
+            namespace: SHO.to_owned(),
+            prefix,
+            metadata,
+            risk_score,
+            hazards,
+            categories,
+        })
+    }
+
+    /// Returns the context shared by all the templates.
+    pub(crate) fn context(&self) -> HashMap<String, Value> {
+        let mut context = HashMap::new();
+
+        context.insert(
+            "namespace".to_string(),
+            Value::from(self.namespace.as_str()),
+        );
+        context.insert("prefix".to_string(), Value::from_serializable(&self.prefix));
+        context.insert(
+            "ontology".to_string(),
+            Value::from_serializable(&self.metadata),
+        );
+        context.insert(
+            "risk_score".to_string(),
+            Value::from_serializable(&self.risk_score),
+        );
+        context.insert(
+            "hazards".to_string(),
+            Value::from_serializable(&self.hazards),
+        );
+        context.insert(
+            "categories".to_string(),
+            Value::from_serializable(&self.categories),
+        );
+
+        context
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn read(document: &str) -> Result<Ontology, OntologyError> {
+        let document = serde_json::from_str(document).unwrap();
+        Ontology::from_graph(&Graph::from_json(&document)?)
+    }
+
+    #[test]
+    fn prefixes() {
+        let document = include_str!("../ontology.jsonld");
+        let ontology = read(document).unwrap();
+
+        assert_eq!(ontology.metadata.version, "v1.0.1");
+        assert_eq!(ontology.risk_score, RiskScoreRange { min: 0, max: 10 });
+        let fire = ontology
+            .hazards
+            .iter()
+            .find(|hazard| hazard.name == "FireHazard")
+            .unwrap();
+        assert_eq!(fire.category, "Safety");
+        assert_eq!(fire.description, "The execution may cause fire");
+        assert!(fire.has_risk_score);
+
+        // Same ontology with a different prefix for the SIFIS namespace
+        let renamed = read(
+            &document
+                .replace("\"sho:", "\"haz:")
+                .replace("\"sho\"", "\"haz\""),
+        )
+        .unwrap();
+        assert_eq!(renamed.hazards, ontology.hazards);
+        assert_eq!(renamed.categories, ontology.categories);
+        assert_eq!(ontology.prefix.as_deref(), Some("sho"));
+        assert_eq!(renamed.prefix.as_deref(), Some("haz"));
+    }
+
+    #[test]
+    fn diagnostics() {
+        let err = read(
+            r#"{
+                "@context": { "sho": "https://purl.org/sifis/hazards#" },
+                "@graph": [{
+                    "@id": "sho:FireHazard",
+                    "@type": "sho:Hazard",
+                    "sho:hasCategory": { "@id": "sho:Safety" }
+                }]
+            }"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.node, "https://purl.org/sifis/hazards#FireHazard");
+        assert!(err.message.contains("undefined category"));
+
+        let err = read(
+            r#"[{
+                "@id": "https://purl.org/sifis/hazards#FireHazard",
+                "@type": "https://purl.org/sifis/hazards#Hazard"
+            }]"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.message, "missing `hasCategory`");
+
+        let err = read(
+            r#"{
+                "@context": { "sho": "https://purl.org/sifis/hazards#" },
+                "@graph": [{
+                    "@id": "sho:Safety",
+                    "@type": "sho:Category"
+                }, {
+                    "@id": "sho:FireHazard",
+                    "@type": "sho:Hazard",
+                    "sho:hasCategory": { "@id": "sho:Safety" },
+                    "sho:riskScore": 11
+                }]
+            }"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.node, "https://purl.org/sifis/hazards#FireHazard");
+        assert_eq!(err.message, "risk score 11 outside 0..=10");
+    }
+}
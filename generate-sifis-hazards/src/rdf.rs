@@ -0,0 +1,239 @@
+//! Reading of the ontology from its serializations.
+
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use arg_enum_proc_macro::ArgEnum;
+use oxrdf::{NamedOrBlankNode, Term, Triple};
+use oxrdfxml::RdfXmlParser;
+use oxttl::{NTriplesParser, TurtleParser};
+
+use crate::graph::{Graph, Object};
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+/// Supported ontology formats
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// JSON-LD
+    #[arg_enum(name = "jsonld")]
+    JsonLd,
+    /// Turtle
+    #[arg_enum(name = "turtle")]
+    Turtle,
+    /// RDF/XML
+    #[arg_enum(name = "rdfxml")]
+    RdfXml,
+    /// N-Triples
+    #[arg_enum(name = "ntriples")]
+    NTriples,
+}
+
+impl Format {
+    /// Detects the format of an ontology from its file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "jsonld" | "json" => Some(Self::JsonLd),
+            "ttl" => Some(Self::Turtle),
+            "rdf" | "owl" | "xml" => Some(Self::RdfXml),
+            "nt" => Some(Self::NTriples),
+            _ => None,
+        }
+    }
+}
+
+fn subject(subject: NamedOrBlankNode) -> String {
+    match subject {
+        NamedOrBlankNode::NamedNode(node) => node.into_string(),
+        NamedOrBlankNode::BlankNode(node) => format!("_:{}", node.as_str()),
+    }
+}
+
+fn object(term: Term) -> Object {
+    match term {
+        Term::NamedNode(node) => Object::Iri(node.into_string()),
+        Term::BlankNode(node) => Object::Iri(format!("_:{}", node.as_str())),
+        Term::Literal(literal) => {
+            let datatype = literal.datatype().as_str();
+            let datatype = (datatype != XSD_STRING && datatype != RDF_LANG_STRING)
+                .then(|| datatype.to_owned());
+            Object::Literal {
+                value: literal.value().to_owned(),
+                lang: literal.language().map(str::to_owned),
+                datatype,
+            }
+        }
+    }
+}
+
+impl Graph {
+    // Reads the triples of a parser, recording the prefixes in scope after
+    // each of them with `add_prefixes`.
+    fn from_triples<P, E>(mut parser: P, add_prefixes: impl Fn(&mut Self, &P)) -> Result<Self>
+    where
+        P: Iterator<Item = Result<Triple, E>>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut graph = Self::default();
+        while let Some(triple) = parser.next() {
+            let triple = triple?;
+            graph.add(
+                &subject(triple.subject),
+                triple.predicate.as_str(),
+                object(triple.object),
+            );
+            // RDF/XML prefixes go out of scope along with their element
+            add_prefixes(&mut graph, &parser);
+        }
+        graph.fold_lists();
+
+        Ok(graph)
+    }
+
+    /// Reads the graph of an ontology serialized in the given format.
+    pub(crate) fn read(format: Format, reader: impl Read) -> Result<Self> {
+        Ok(match format {
+            Format::JsonLd => {
+                let document: serde_json::Value = serde_json::from_reader(reader)?;
+                Self::from_json(&document)?
+            }
+            Format::Turtle => {
+                Self::from_triples(TurtleParser::new().for_reader(reader), |graph, parser| {
+                    graph.add_prefixes(parser.prefixes())
+                })?
+            }
+            Format::RdfXml => {
+                Self::from_triples(RdfXmlParser::new().for_reader(reader), |graph, parser| {
+                    graph.add_prefixes(parser.prefixes())
+                })?
+            }
+            Format::NTriples => {
+                Self::from_triples(NTriplesParser::new().for_reader(reader), |_, _| {})?
+            }
+        })
+    }
+
+    /// Reads the graph of an ontology file.
+    ///
+    /// The format is detected from the file extension when not given.
+    pub(crate) fn open(path: &Path, format: Option<Format>) -> Result<Self> {
+        let format = format
+            .or_else(|| Format::from_path(path))
+            .with_context(|| {
+                format!(
+                    "Cannot detect the format of {}, please specify it",
+                    path.display()
+                )
+            })?;
+        let file = std::fs::File::open(path)?;
+
+        Self::read(format, std::io::BufReader::new(file))
+            .with_context(|| format!("Cannot parse {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ontology::Ontology;
+
+    const TURTLE: &str = r#"
+        @prefix haz: <https://purl.org/sifis/hazards#> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix owl: <http://www.w3.org/2002/07/owl#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        haz:level owl:equivalentClass [
+            owl:withRestrictions ( [ xsd:minInclusive 0 ] [ xsd:maxInclusive 10 ] )
+        ] .
+        haz:Safety a haz:Category ;
+            haz:description "Category which includes all the safety-related hazards." .
+        haz:FireHazard a haz:Hazard ;
+            rdfs:label "FireHazard"@en ;
+            haz:description "The execution may cause fire" ;
+            haz:hasCategory haz:Safety ;
+            haz:riskScore "" .
+    "#;
+
+    const RDF_XML: &str = r#"<?xml version="1.0"?>
+        <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                 xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+                 xmlns:owl="http://www.w3.org/2002/07/owl#"
+                 xmlns:xsd="http://www.w3.org/2001/XMLSchema#"
+                 xmlns:sho="https://purl.org/sifis/hazards#">
+          <rdf:Description rdf:about="https://purl.org/sifis/hazards#level">
+            <owl:equivalentClass>
+              <rdf:Description>
+                <owl:withRestrictions rdf:parseType="Collection">
+                  <rdf:Description><xsd:minInclusive>0</xsd:minInclusive></rdf:Description>
+                  <rdf:Description><xsd:maxInclusive>10</xsd:maxInclusive></rdf:Description>
+                </owl:withRestrictions>
+              </rdf:Description>
+            </owl:equivalentClass>
+          </rdf:Description>
+          <sho:Category rdf:about="https://purl.org/sifis/hazards#Safety">
+            <sho:description>Category which includes all the safety-related hazards.</sho:description>
+          </sho:Category>
+          <sho:Hazard rdf:about="https://purl.org/sifis/hazards#FireHazard">
+            <rdfs:label xml:lang="en">FireHazard</rdfs:label>
+            <sho:description>The execution may cause fire</sho:description>
+            <sho:hasCategory rdf:resource="https://purl.org/sifis/hazards#Safety"/>
+            <sho:riskScore></sho:riskScore>
+          </sho:Hazard>
+        </rdf:RDF>"#;
+
+    const N_TRIPLES: &str = r#"
+<https://purl.org/sifis/hazards#level> <http://www.w3.org/2002/07/owl#equivalentClass> _:d .
+_:d <http://www.w3.org/2002/07/owl#withRestrictions> _:l0 .
+_:l0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> _:min .
+_:l0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:l1 .
+_:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> _:max .
+_:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .
+_:min <http://www.w3.org/2001/XMLSchema#minInclusive> "0"^^<http://www.w3.org/2001/XMLSchema#integer> .
+_:max <http://www.w3.org/2001/XMLSchema#maxInclusive> "10"^^<http://www.w3.org/2001/XMLSchema#integer> .
+<https://purl.org/sifis/hazards#Safety> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://purl.org/sifis/hazards#Category> .
+<https://purl.org/sifis/hazards#Safety> <https://purl.org/sifis/hazards#description> "Category which includes all the safety-related hazards." .
+<https://purl.org/sifis/hazards#FireHazard> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://purl.org/sifis/hazards#Hazard> .
+<https://purl.org/sifis/hazards#FireHazard> <http://www.w3.org/2000/01/rdf-schema#label> "FireHazard"@en .
+<https://purl.org/sifis/hazards#FireHazard> <https://purl.org/sifis/hazards#description> "The execution may cause fire" .
+<https://purl.org/sifis/hazards#FireHazard> <https://purl.org/sifis/hazards#hasCategory> <https://purl.org/sifis/hazards#Safety> .
+<https://purl.org/sifis/hazards#FireHazard> <https://purl.org/sifis/hazards#riskScore> "" .
+"#;
+
+    fn read(format: Format, document: &str) -> Ontology {
+        Ontology::from_graph(&Graph::read(format, document.as_bytes()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn formats() {
+        let turtle = read(Format::Turtle, TURTLE);
+        assert_eq!(turtle.risk_score.max, 10);
+        assert_eq!(turtle.hazards.len(), 1);
+        assert_eq!(turtle.hazards[0].category, "Safety");
+        assert!(turtle.hazards[0].has_risk_score);
+        assert_eq!(turtle.categories[0].hazards, ["FireHazard"]);
+
+        let rdf_xml = read(Format::RdfXml, RDF_XML);
+        assert_eq!(rdf_xml.hazards, turtle.hazards);
+        assert_eq!(rdf_xml.categories, turtle.categories);
+        assert_eq!(rdf_xml.risk_score, turtle.risk_score);
+
+        let n_triples = read(Format::NTriples, N_TRIPLES);
+        assert_eq!(n_triples.hazards, turtle.hazards);
+        assert_eq!(n_triples.risk_score, turtle.risk_score);
+
+        assert_eq!(turtle.prefix.as_deref(), Some("haz"));
+        assert_eq!(rdf_xml.prefix.as_deref(), Some("sho"));
+        assert_eq!(n_triples.prefix, None);
+
+        assert_eq!(
+            Format::from_path(Path::new("hazards.owl")),
+            Some(Format::RdfXml)
+        );
+        assert_eq!(Format::from_path(Path::new("hazards")), None);
+        assert!(Graph::read(Format::Turtle, "haz:FireHazard a".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Templates loaded from a directory.
+//!
+//! The directory contains a `templates.toml` manifest which maps each
+//! generated file, relative to the output directory, to the template
+//! rendering it:
+//!
+//! ```toml
+//! [files]
+//! "hazards/hazards.go" = "hazards.go.j2"
+//! ```
+//!
+//! Every other file of the directory is loaded as a template as well, so
+//! it can be included or extended by the rendered ones. Templates are
+//! named after their path relative to the directory and receive the same
+//! context and filters as the built-in ones.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use minijinja::Source;
+use serde::Deserialize;
+
+use crate::ontology::Ontology;
+use crate::SifisTemplate;
+
+/// Name of the manifest of a template directory.
+const MANIFEST: &str = "templates.toml";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    files: BTreeMap<PathBuf, String>,
+}
+
+/// Templates of a directory
+#[derive(Clone, Debug)]
+pub(crate) struct TemplateDir {
+    path: PathBuf,
+    files: BTreeMap<PathBuf, String>,
+    source: Source,
+}
+
+// Adds the files of a directory to the source, skipping hidden files.
+fn load_templates(source: &mut Source, root: &Path, dir: &Path) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+
+        if path.is_dir() {
+            load_templates(source, root, &path)?;
+        } else if path != root.join(MANIFEST) {
+            // Template names use `/` as separator on every platform
+            let name = path
+                .strip_prefix(root)?
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let template = fs::read_to_string(&path)
+                .with_context(|| format!("Cannot read {}", path.display()))?;
+            source
+                .add_template(name, template)
+                .with_context(|| format!("Invalid template {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+impl TemplateDir {
+    /// Reads the manifest and the templates of a directory.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let manifest_path = path.join(MANIFEST);
+        let manifest = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Cannot read {}", manifest_path.display()))?;
+        let Manifest { files } = toml::from_str(&manifest)
+            .with_context(|| format!("Invalid manifest {}", manifest_path.display()))?;
+
+        let mut source = Source::new();
+        load_templates(&mut source, path, path)?;
+
+        for (output, template) in &files {
+            // Generated files must stay within the output directory
+            if !output
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                bail!(
+                    "{}: `{}` is not a relative path within the output directory",
+                    manifest_path.display(),
+                    output.display()
+                );
+            }
+            if !path.join(template).is_file() {
+                bail!(
+                    "{}: template `{}` of `{}` not found",
+                    manifest_path.display(),
+                    template,
+                    output.display()
+                );
+            }
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            files,
+            source,
+        })
+    }
+
+    pub(crate) fn build(&self, ontology: &Ontology) -> SifisTemplate {
+        SifisTemplate {
+            context: ontology.context(),
+            files: self
+                .files
+                .iter()
+                .map(|(path, template)| (path.clone(), template.clone()))
+                .collect::<HashMap<_, _>>(),
+            source: self.source.clone(),
+            format: |_, content| Ok(content),
+        }
+    }
+}
+
+impl fmt::Display for TemplateDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.path.display())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn template_dir(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        dir
+    }
+
+    #[test]
+    fn render() {
+        let dir = template_dir(&[
+            (
+                MANIFEST,
+                "[files]\n\"out/hazards.txt\" = \"hazards.txt.j2\"\n",
+            ),
+            (
+                "hazards.txt.j2",
+                "{% include \"partials/header.j2\" %}\n\
+                 {% for hazard in hazards %}{{ hazard.name | screaming_snake_case }} \
+                 {{ hazard.category }}\n{% endfor %}",
+            ),
+            ("partials/header.j2", "{{ ontology.version }}"),
+        ]);
+        let ontology = Ontology::open(
+            Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/ontology.jsonld")),
+            None,
+        )
+        .unwrap();
+
+        let rendered = TemplateDir::open(dir.path())
+            .unwrap()
+            .build(&ontology)
+            .render()
+            .unwrap();
+
+        let hazards = &rendered[Path::new("out/hazards.txt")];
+        assert!(hazards.starts_with("v1.0.1\nAIR_POISONING Safety\n"));
+        assert_eq!(rendered.len(), 1);
+    }
+
+    #[test]
+    fn invalid_manifests() {
+        let error = |manifest| {
+            let dir = template_dir(&[(MANIFEST, manifest), ("a.j2", "")]);
+            let error = TemplateDir::open(dir.path()).unwrap_err();
+            format!("{:#}", error)
+        };
+
+        assert!(error("[files]\n\"../a\" = \"a.j2\"\n")
+            .ends_with("`../a` is not a relative path within the output directory"));
+        assert!(error("[files]\n\"/a\" = \"a.j2\"\n")
+            .ends_with("`/a` is not a relative path within the output directory"));
+        assert!(error("[files]\n\"a\" = \"b.j2\"\n").ends_with("template `b.j2` of `a` not found"));
+        assert!(error("files = 1\n").contains("Invalid manifest"));
+        assert!(TemplateDir::open(Path::new("/nonexistent")).is_err());
+    }
+}
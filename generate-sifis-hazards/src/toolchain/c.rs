@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use minijinja::value::Value;
+
+use crate::ontology::Ontology;
+use crate::{builtin_templates, BuildTemplate};
+
+static C_TEMPLATES: &[(&str, &str)] = &builtin_templates!["c" =>
+    ("c.hazards", "hazards.h")
+];
+
+pub(crate) struct C;
+
+impl C {
+    pub(crate) fn create() -> Self {
+        Self
+    }
+
+    fn project_structure() -> HashMap<PathBuf, &'static str> {
+        let output = Path::new("include");
+
+        let mut template_files = HashMap::new();
+
+        template_files.insert(output.join("sifis_hazards.h"), "c.hazards");
+
+        template_files
+    }
+}
+
+impl BuildTemplate for C {
+    fn define(
+        &self,
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>) {
+        (C::project_structure(), ontology.context())
+    }
+
+    fn get_templates() -> &'static [(&'static str, &'static str)] {
+        C_TEMPLATES
+    }
+
+    // Names are always prefixed in the generated identifiers.
+    fn is_keyword(_name: &str) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::process::Command;
+
+    use super::*;
+
+    use crate::graph::Graph;
+    use crate::rdf::Format;
+
+    // Renders the header of an ontology written in Turtle.
+    fn render(turtle: &str) -> String {
+        let graph = Graph::read(Format::Turtle, turtle.as_bytes()).unwrap();
+        let ontology = Ontology::from_graph(&graph).unwrap();
+
+        C::create()
+            .build(&ontology)
+            .render()
+            .unwrap()
+            .remove(Path::new("include/sifis_hazards.h"))
+            .unwrap()
+    }
+
+    #[test]
+    fn escaping() {
+        let header = render(
+            r#"
+            @prefix sho: <https://purl.org/sifis/hazards#> .
+
+            sho:Safety a sho:Category ;
+                sho:description "Ends */ here,\nopens /* there" .
+            sho:Fire a sho:Hazard ;
+                sho:description "A \"quoted\" C:\\path */,\nsecond line" ;
+                sho:hasCategory sho:Safety .
+            "#,
+        );
+
+        assert!(header.contains("    /** Ends * / here,\n     * opens / * there */\n"));
+        assert!(header.contains(
+            "    /** A \"quoted\" C:\\path * /,\n     * second line */\n    SIFIS_HAZARD_FIRE,\n"
+        ));
+        assert!(header.contains(r#"    "A \"quoted\" C:\\path */,\nsecond line","#));
+    }
+
+    #[test]
+    fn compiles() {
+        let header = render(
+            r#"
+            @prefix sho: <https://purl.org/sifis/hazards#> .
+
+            sho:Safety a sho:Category ; sho:description "*/\r\n#define SAFETY 1\n//" .
+            sho:Fire a sho:Hazard ;
+                sho:description "Quote \" backslash \\ ??/\n*/ closes, /* opens,\ttab \u0007bell ??= né \\" ;
+                sho:hasCategory sho:Safety .
+            "#,
+        );
+
+        assert!(header.ends_with("#endif /* SIFIS_HAZARDS_H */\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("sifis_hazards.h"), header).unwrap();
+        fs::write(
+            dir.path().join("main.c"),
+            r#"
+            #include <stdio.h>
+            #include "sifis_hazards.h"
+
+            int main(void)
+            {
+                printf("%s|%s", sifis_category_description(SIFIS_CATEGORY_SAFETY),
+                       sifis_hazard_description(SIFIS_HAZARD_FIRE));
+                return 0;
+            }
+            "#,
+        )
+        .unwrap();
+
+        let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+        let output = Command::new(cc)
+            .args(["-std=c99", "-Wall", "-Wextra", "-pedantic", "-Werror", "-o"])
+            .arg(dir.path().join("main"))
+            .arg(dir.path().join("main.c"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let output = Command::new(dir.path().join("main")).output().unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "*/\r\n#define SAFETY 1\n//|\
+             Quote \" backslash \\ ??/\n*/ closes, /* opens,\ttab \u{7}bell ??= né \\"
+        );
+    }
+}
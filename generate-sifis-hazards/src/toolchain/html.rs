@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use minijinja::value::Value;
+
+use crate::ontology::Ontology;
+use crate::{builtin_templates, BuildTemplate};
+
+// The extension of the template name enables HTML escaping.
+static HTML_TEMPLATES: &[(&str, &str)] = &builtin_templates!["html" =>
+    ("catalogue.html", "catalogue.html")
+];
+
+pub(crate) struct Html;
+
+impl Html {
+    pub(crate) fn create() -> Self {
+        Self
+    }
+
+    fn project_structure() -> HashMap<PathBuf, &'static str> {
+        let output = Path::new("docs");
+
+        let mut template_files = HashMap::new();
+
+        template_files.insert(output.join("hazards.html"), "catalogue.html");
+
+        template_files
+    }
+}
+
+impl BuildTemplate for Html {
+    fn define(
+        &self,
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>) {
+        (Html::project_structure(), ontology.context())
+    }
+
+    fn get_templates() -> &'static [(&'static str, &'static str)] {
+        HTML_TEMPLATES
+    }
+
+    // Names are only used as text.
+    fn is_keyword(_name: &str) -> bool {
+        false
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use minijinja::value::Value;
+
+use crate::ontology::Ontology;
+use crate::{builtin_templates, BuildTemplate};
+
+// The OpenAPI fragment holds the `components/schemas` of OpenAPI 3.1, whose
+// schemas are JSON Schema 2020-12 ones.
+static JSON_SCHEMA_TEMPLATES: &[(&str, &str)] = &builtin_templates!["json_schema" =>
+    ("json.schema", "hazards.schema.json"),
+    ("json.openapi", "openapi.json")
+];
+
+pub(crate) struct JsonSchema;
+
+impl JsonSchema {
+    pub(crate) fn create() -> Self {
+        Self
+    }
+
+    fn project_structure() -> HashMap<PathBuf, &'static str> {
+        let output = Path::new("schemas");
+
+        let mut template_files = HashMap::new();
+
+        template_files.insert(output.join("hazards.schema.json"), "json.schema");
+        template_files.insert(output.join("openapi.json"), "json.openapi");
+
+        template_files
+    }
+}
+
+impl BuildTemplate for JsonSchema {
+    fn define(
+        &self,
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>) {
+        (JsonSchema::project_structure(), ontology.context())
+    }
+
+    fn get_templates() -> &'static [(&'static str, &'static str)] {
+        JSON_SCHEMA_TEMPLATES
+    }
+
+    // Names are only used as text.
+    fn is_keyword(_name: &str) -> bool {
+        false
+    }
+}
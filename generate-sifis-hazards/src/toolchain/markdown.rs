@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use minijinja::value::Value;
+
+use crate::ontology::Ontology;
+use crate::{builtin_templates, BuildTemplate};
+
+static MARKDOWN_TEMPLATES: &[(&str, &str)] = &builtin_templates!["markdown" =>
+    ("md.catalogue", "catalogue.md")
+];
+
+pub(crate) struct Markdown;
+
+impl Markdown {
+    pub(crate) fn create() -> Self {
+        Self
+    }
+
+    fn project_structure() -> HashMap<PathBuf, &'static str> {
+        let output = Path::new("docs");
+
+        let mut template_files = HashMap::new();
+
+        template_files.insert(output.join("hazards.md"), "md.catalogue");
+
+        template_files
+    }
+}
+
+impl BuildTemplate for Markdown {
+    fn define(
+        &self,
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>) {
+        (Markdown::project_structure(), ontology.context())
+    }
+
+    fn get_templates() -> &'static [(&'static str, &'static str)] {
+        MARKDOWN_TEMPLATES
+    }
+
+    // Names are only used as text.
+    fn is_keyword(_name: &str) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::graph::Graph;
+    use crate::rdf::Format;
+
+    // Renders the catalogue of an ontology written in Turtle.
+    fn render(turtle: &str) -> String {
+        let graph = Graph::read(Format::Turtle, turtle.as_bytes()).unwrap();
+        let ontology = Ontology::from_graph(&graph).unwrap();
+
+        Markdown::create()
+            .build(&ontology)
+            .render()
+            .unwrap()
+            .remove(Path::new("docs/hazards.md"))
+            .unwrap()
+    }
+
+    #[test]
+    fn escaping() {
+        let catalogue = render(
+            r#"
+            @prefix sho: <https://purl.org/sifis/hazards#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+            sho:Safety a sho:Category ;
+                sho:description "Keeps <b>you</b> safe,\n- not a list item" ;
+                rdfs:label "Safety\nfirst"@en .
+            sho:Fire a sho:Hazard ;
+                sho:description "May cause *fire* </script>\n# not a heading" ;
+                sho:hasCategory sho:Safety .
+            "#,
+        );
+
+        assert!(catalogue.contains(
+            "- [Safety](#safety): Keeps &lt;b&gt;you&lt;/b&gt; safe, - not a list item\n"
+        ));
+        assert!(
+            catalogue.contains("\n\nKeeps &lt;b&gt;you&lt;/b&gt; safe,\n\\- not a list item\n\n")
+        );
+        assert!(catalogue.contains("- **Labels:** Safety first (en)\n"));
+        assert!(
+            catalogue.contains("\n\nMay cause \\*fire\\* &lt;/script&gt;\n\\# not a heading\n\n")
+        );
+        assert!(!catalogue.contains("<b>"));
+    }
+}
@@ -1,6 +1,18 @@
+pub mod c;
+pub mod html;
+pub mod json_schema;
+pub mod markdown;
+pub mod python;
 pub mod rust;
+pub mod typescript;
 
+pub(crate) use c::*;
+pub(crate) use html::*;
+pub(crate) use json_schema::*;
+pub(crate) use markdown::*;
+pub(crate) use python::*;
 pub(crate) use rust::*;
+pub(crate) use typescript::*;
 
 #[macro_export]
 macro_rules! builtin_templates {
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use minijinja::value::Value;
+
+use crate::ontology::Ontology;
+use crate::{builtin_templates, BuildTemplate};
+
+static PYTHON_TEMPLATES: &[(&str, &str)] = &builtin_templates!["python" =>
+    ("py.init", "init.py"),
+    ("py.hazards", "hazards.py")
+];
+
+pub(crate) struct Python;
+
+impl Python {
+    pub(crate) fn create() -> Self {
+        Self
+    }
+
+    fn project_structure() -> HashMap<PathBuf, &'static str> {
+        let output = Path::new("sifis_hazards");
+
+        let mut template_files = HashMap::new();
+
+        template_files.insert(output.join("__init__.py"), "py.init");
+        template_files.insert(output.join("hazards.py"), "py.hazards");
+
+        template_files
+    }
+}
+
+impl BuildTemplate for Python {
+    fn define(
+        &self,
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>) {
+        (Python::project_structure(), ontology.context())
+    }
+
+    fn get_templates() -> &'static [(&'static str, &'static str)] {
+        PYTHON_TEMPLATES
+    }
+
+    // Names are upper-cased, so they never clash with the keywords.
+    fn is_keyword(_name: &str) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::process::Command;
+
+    use super::*;
+
+    use crate::graph::Graph;
+    use crate::rdf::Format;
+
+    // Renders the package of an ontology written in Turtle.
+    fn render_files(turtle: &str) -> BTreeMap<PathBuf, String> {
+        let graph = Graph::read(Format::Turtle, turtle.as_bytes()).unwrap();
+        let ontology = Ontology::from_graph(&graph).unwrap();
+
+        Python::create().build(&ontology).render().unwrap()
+    }
+
+    // Renders the hazards module of an ontology written in Turtle.
+    fn render(turtle: &str) -> String {
+        render_files(turtle)
+            .remove(Path::new("sifis_hazards/hazards.py"))
+            .unwrap()
+    }
+
+    #[test]
+    fn escaping() {
+        let module = render(
+            r#"
+            @prefix sho: <https://purl.org/sifis/hazards#> .
+
+            sho:Safety a sho:Category ;
+                sho:description "Ends */ here,\nopens /* there" .
+            sho:Fire a sho:Hazard ;
+                sho:description "A \"quoted\" C:\\path */,\nsecond line" ;
+                sho:hasCategory sho:Safety .
+            "#,
+        );
+
+        assert!(module.contains("    #: Ends * / here,\n    #: opens / * there\n"));
+        assert!(module.contains(
+            "    #: A \"quoted\" C:\\path * /,\n    #: second line\n    FIRE = \"Fire\"\n"
+        ));
+        assert!(module.contains(r#"Hazard.FIRE: "A \"quoted\" C:\\path */,\nsecond line","#));
+    }
+
+    #[test]
+    fn compiles() {
+        let files = render_files(
+            r#"
+            @prefix sho: <https://purl.org/sifis/hazards#> .
+
+            sho:Safety a sho:Category ; sho:description "First\r\n    indented\rlast \\" .
+            sho:Fire a sho:Hazard ;
+                sho:description "Quotes \"\"\" ''' and # hash\n\nafter an empty line\u2028" ;
+                sho:hasCategory sho:Safety .
+            "#,
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            assert!(content.ends_with('\n') && !content.ends_with("\n\n"));
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let output = Command::new("python3")
+            .args(["-m", "py_compile"])
+            .arg(dir.path().join("sifis_hazards/__init__.py"))
+            .arg(dir.path().join("sifis_hazards/hazards.py"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let output = Command::new("python3")
+            .arg("-c")
+            .arg(
+                "from sifis_hazards import Category, Hazard; \
+                 print(Category.SAFETY.description, Hazard.FIRE.description, sep='|', end='')",
+            )
+            .current_dir(dir.path())
+            .env("PYTHONIOENCODING", "utf-8")
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "First\r\n    indented\rlast \\|\
+             Quotes \"\"\" ''' and # hash\n\nafter an empty line\u{2028}",
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -1,15 +1,43 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use anyhow::{anyhow, Result};
 use minijinja::value::Value;
-use serde::Serialize;
 
-use crate::{builtin_templates, BuildTemplate, Ontology};
+use crate::ontology::Ontology;
+use crate::{builtin_templates, BuildTemplate};
+
+// Strict and reserved keywords, which cannot be used as enum variants.
+static RUST_KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
 
 static RUST_TEMPLATES: &[(&str, &str)] = &builtin_templates!["rust" =>
     ("rs.api", "api.rs")
 ];
 
+// Checks that the generated code parses and formats it with prettyplease,
+// which gives the same output on every machine, unlike the installed
+// rustfmt, if any.
+fn format_rust(path: &Path, code: String) -> Result<String> {
+    let file = syn::parse_file(&code).map_err(|e| {
+        let start = e.span().start();
+        anyhow!(
+            "Generated Rust code {} is not valid, line {}, column {}: {}",
+            path.display(),
+            start.line,
+            start.column + 1,
+            e
+        )
+    })?;
+
+    Ok(prettyplease::unparse(&file))
+}
+
 pub(crate) struct Rust;
 
 impl Rust {
@@ -17,106 +45,121 @@ impl Rust {
         Self
     }
 
-    fn project_structure(output_path: &Path) -> (HashMap<PathBuf, &'static str>, Vec<PathBuf>) {
-        let output = output_path.to_path_buf().join("src");
+    fn project_structure() -> HashMap<PathBuf, &'static str> {
+        let output = Path::new("src");
 
         let mut template_files = HashMap::new();
 
         template_files.insert(output.join("ontology.rs"), "rs.api");
 
-        (template_files, vec![output])
+        template_files
     }
 }
 
-#[derive(Serialize)]
-struct HazardData {
-    description: String,
-    name: String,
-    category: String,
-}
-
-#[derive(Serialize)]
-struct CategoryData {
-    description: String,
-    name: String,
-    hazards: Vec<String>,
-}
-
 impl BuildTemplate for Rust {
     fn define(
         &self,
-        ontology: Ontology,
-        output_path: &Path,
-    ) -> (
-        HashMap<PathBuf, &'static str>,
-        Vec<PathBuf>,
-        HashMap<String, Value>,
-    ) {
-        let mut context = HashMap::new();
-        let mut hazards = Vec::new();
-        let mut categories_hazards = HashMap::new();
-        let mut categories = Vec::new();
-
-        for object in ontology.graph {
-            if let serde_json::Value::Object(object_value) = object {
-                if let Some(serde_json::Value::Object(object_type)) = object_value.get("rdf:type") {
-                    let id = object_value
-                        .get("@id")
-                        .unwrap()
-                        .as_str()
-                        .unwrap_or_default()
-                        .trim_start_matches("sho:");
-                    let description = object_value
-                        .get("description")
-                        .unwrap()
-                        .as_str()
-                        .unwrap_or_default();
-                    if object_type.get("@id").map_or(false, |v| v == "sho:Hazard") {
-                        let has_category = object_value
-                            .get("hasCategory")
-                            .unwrap()
-                            .as_str()
-                            .unwrap_or_default()
-                            .trim_start_matches("sho:");
-                        hazards.push(HazardData {
-                            description: description.to_owned(),
-                            name: id.to_owned(),
-                            category: has_category.to_owned(),
-                        });
-                        categories_hazards
-                            .entry(has_category.to_owned())
-                            .or_insert_with(Vec::new)
-                            .push(id.to_owned());
-                    } else if object_type
-                        .get("@id")
-                        .map_or(false, |v| v == "sho:Category")
-                    {
-                        categories.push(CategoryData {
-                            description: description.to_owned(),
-                            name: id.to_owned(),
-                            hazards: Vec::new(),
-                        });
-                    }
-                }
-            }
-        }
-
-        categories.iter_mut().for_each(|category| {
-            category.hazards = categories_hazards.get(&category.name).unwrap().to_owned();
-        });
-
-        context.insert("hazards".to_string(), Value::from_serializable(&hazards));
-        context.insert(
-            "categories".to_string(),
-            Value::from_serializable(&categories),
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>) {
+        (Rust::project_structure(), ontology.context())
+    }
+
+    fn get_templates() -> &'static [(&'static str, &'static str)] {
+        RUST_TEMPLATES
+    }
+
+    fn is_keyword(name: &str) -> bool {
+        RUST_KEYWORDS.contains(&name)
+    }
+
+    fn format(path: &Path, content: String) -> Result<String> {
+        format_rust(path, content)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use minijinja::value::Value;
+
+    use crate::graph::Graph;
+    use crate::lockfile::Lockfile;
+    use crate::rdf::Format;
+
+    // Renders the API of an ontology written in Turtle.
+    fn render(turtle: &str) -> String {
+        let graph = Graph::read(Format::Turtle, turtle.as_bytes()).unwrap();
+        let ontology = Ontology::from_graph(&graph).unwrap();
+
+        let mut lockfile = Lockfile::default();
+        lockfile.update(&ontology.hazards).unwrap();
+        let mut template = Rust::create().build(&ontology);
+        template.context.insert(
+            "hazard_bits".to_string(),
+            Value::from_serializable(&lockfile.hazards),
+        );
+
+        template
+            .render()
+            .unwrap()
+            .remove(Path::new("src/ontology.rs"))
+            .unwrap()
+    }
+
+    #[test]
+    fn escaping() {
+        let api = render(
+            r#"
+            @prefix sho: <https://purl.org/sifis/hazards#> .
+
+            sho:Safety a sho:Category ; sho:description "Safety" .
+            sho:Fire a sho:Hazard ;
+                sho:description "A \"quoted\" C:\\path,\nsecond line" ;
+                sho:hasCategory sho:Safety .
+            "#,
         );
 
-        let (files, dirs) = Rust::project_structure(output_path);
+        assert!(api.contains(r#"Self::Fire => "A \"quoted\" C:\\path,\nsecond line","#));
+        assert!(api.contains("    /// A \"quoted\" C:\\path,\n    /// second line\n    Fire,\n"));
+    }
 
-        (files, dirs, context)
+    #[test]
+    fn prefix_and_risk_scores() {
+        let ontology = r#"
+            @prefix haz: <https://purl.org/sifis/hazards#> .
+
+            haz:Safety a haz:Category ; haz:description "Safety" .
+            haz:Fire a haz:Hazard ;
+                haz:description "Fire" ;
+                haz:hasCategory haz:Safety ;
+                haz:riskScore 7 .
+            "#;
+
+        let api = render(&format!(
+            "{}haz:Smoke a haz:Hazard ; haz:description \"Smoke\" ; haz:hasCategory haz:Safety .",
+            ontology
+        ));
+        assert!(api.contains(r#"const PREFIXES: &[&str] = &["haz:", ONTOLOGY_NAMESPACE];"#));
+        assert!(api.contains(
+            "        match self {\n            Self::Fire => Some(7),\n            _ => None,\n        }"
+        ));
+
+        let api = render(ontology);
+        assert!(api.contains("        match self {\n            Self::Fire => Some(7),\n        }"));
+
+        let api = render(&ontology.replace("haz:riskScore 7", "haz:riskScore \"\""));
+        assert!(api
+            .contains("    pub fn default_risk_score(&self) -> Option<u8> {\n        None\n    }"));
     }
 
-    fn get_templates() -> &'static [(&'static str, &'static str)] {
-        RUST_TEMPLATES
+    #[test]
+    fn invalid_code() {
+        let error = format_rust(Path::new("src/ontology.rs"), "fn f() {\n    1 +\n}".into());
+
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Generated Rust code src/ontology.rs is not valid, line 3, column 1: \
+             unexpected end of input, expected an expression"
+        );
     }
 }
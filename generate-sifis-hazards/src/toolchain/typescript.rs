@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use minijinja::value::Value;
+
+use crate::ontology::Ontology;
+use crate::{builtin_templates, BuildTemplate};
+
+static TYPESCRIPT_TEMPLATES: &[(&str, &str)] = &builtin_templates!["typescript" =>
+    ("ts.index", "index.ts"),
+    ("ts.hazards", "hazards.ts")
+];
+
+pub(crate) struct TypeScript;
+
+impl TypeScript {
+    pub(crate) fn create() -> Self {
+        Self
+    }
+
+    fn project_structure() -> HashMap<PathBuf, &'static str> {
+        let output = Path::new("src");
+
+        let mut template_files = HashMap::new();
+
+        template_files.insert(output.join("index.ts"), "ts.index");
+        template_files.insert(output.join("hazards.ts"), "ts.hazards");
+
+        template_files
+    }
+}
+
+impl BuildTemplate for TypeScript {
+    fn define(
+        &self,
+        ontology: &Ontology,
+    ) -> (HashMap<PathBuf, &'static str>, HashMap<String, Value>) {
+        (TypeScript::project_structure(), ontology.context())
+    }
+
+    fn get_templates() -> &'static [(&'static str, &'static str)] {
+        TYPESCRIPT_TEMPLATES
+    }
+
+    // Names are only used as string literals and property names.
+    fn is_keyword(_name: &str) -> bool {
+        false
+    }
+}
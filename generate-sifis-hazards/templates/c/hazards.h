@@ -0,0 +1,144 @@
+/*
+ * SIFIS hazards ontology {{ ontology.version }}.
+ *
+ * Requires C99 or C++.
+ *
+ * Generated by generate-sifis-hazards, do not edit.
+ */
+
+#ifndef SIFIS_HAZARDS_H
+#define SIFIS_HAZARDS_H
+
+#include <stddef.h>
+#include <string.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** IRI of the SIFIS hazards ontology. */
+#define SIFIS_ONTOLOGY_IRI "{{ ontology.iri | escape_c }}"
+
+/** Namespace of the SIFIS hazards ontology. */
+#define SIFIS_ONTOLOGY_NAMESPACE "{{ namespace | escape_c }}"
+
+/** Version of the SIFIS hazards ontology. */
+#define SIFIS_ONTOLOGY_VERSION "{{ ontology.version | escape_c }}"
+
+/** Lowest risk score admitted by the ontology. */
+#define SIFIS_RISK_SCORE_MIN {{ risk_score.min }}
+
+/** Highest risk score admitted by the ontology. */
+#define SIFIS_RISK_SCORE_MAX {{ risk_score.max }}
+
+/** Categories associated to an hazard. */
+enum sifis_category {
+{%- for category in categories %}
+    /** {{ category.description | comment("     * ") }} */
+    SIFIS_CATEGORY_{{ category.name | screaming_snake_case }},
+{%- endfor %}
+    /** Number of categories, also used for unknown categories. */
+    SIFIS_CATEGORY_COUNT
+};
+
+/** Hazards type. */
+enum sifis_hazard {
+{%- for hazard in hazards %}
+    /** {{ hazard.description | comment("     * ") }} */
+    SIFIS_HAZARD_{{ hazard.name | screaming_snake_case }},
+{%- endfor %}
+    /** Number of hazards, also used for unknown hazards. */
+    SIFIS_HAZARD_COUNT
+};
+
+static const char *const sifis_category_names[SIFIS_CATEGORY_COUNT] = {
+{%- for category in categories %}
+    "{{ category.name | escape_c }}",
+{%- endfor %}
+};
+
+static const char *const sifis_category_descriptions[SIFIS_CATEGORY_COUNT] = {
+{%- for category in categories %}
+    "{{ category.description | escape_c }}",
+{%- endfor %}
+};
+
+static const char *const sifis_hazard_names[SIFIS_HAZARD_COUNT] = {
+{%- for hazard in hazards %}
+    "{{ hazard.name | escape_c }}",
+{%- endfor %}
+};
+
+static const char *const sifis_hazard_descriptions[SIFIS_HAZARD_COUNT] = {
+{%- for hazard in hazards %}
+    "{{ hazard.description | escape_c }}",
+{%- endfor %}
+};
+
+static const enum sifis_category sifis_hazard_categories[SIFIS_HAZARD_COUNT] = {
+{%- for hazard in hazards %}
+    SIFIS_CATEGORY_{{ hazard.category | screaming_snake_case }},
+{%- endfor %}
+};
+
+/** Returns the name of a category, or NULL if unknown. */
+static inline const char *sifis_category_name(enum sifis_category category)
+{
+    return (size_t)category < SIFIS_CATEGORY_COUNT ? sifis_category_names[category] : NULL;
+}
+
+/** Returns the description of a category, or NULL if unknown. */
+static inline const char *sifis_category_description(enum sifis_category category)
+{
+    return (size_t)category < SIFIS_CATEGORY_COUNT ? sifis_category_descriptions[category] : NULL;
+}
+
+/** Returns the category with the given name, or SIFIS_CATEGORY_COUNT if unknown. */
+static inline enum sifis_category sifis_category_from_name(const char *name)
+{
+    size_t i;
+
+    for (i = 0; i < SIFIS_CATEGORY_COUNT; i++) {
+        if (strcmp(sifis_category_names[i], name) == 0) {
+            return (enum sifis_category)i;
+        }
+    }
+    return SIFIS_CATEGORY_COUNT;
+}
+
+/** Returns the name of a hazard, or NULL if unknown. */
+static inline const char *sifis_hazard_name(enum sifis_hazard hazard)
+{
+    return (size_t)hazard < SIFIS_HAZARD_COUNT ? sifis_hazard_names[hazard] : NULL;
+}
+
+/** Returns the description of a hazard, or NULL if unknown. */
+static inline const char *sifis_hazard_description(enum sifis_hazard hazard)
+{
+    return (size_t)hazard < SIFIS_HAZARD_COUNT ? sifis_hazard_descriptions[hazard] : NULL;
+}
+
+/** Returns the category of a hazard, or SIFIS_CATEGORY_COUNT if unknown. */
+static inline enum sifis_category sifis_hazard_category(enum sifis_hazard hazard)
+{
+    return (size_t)hazard < SIFIS_HAZARD_COUNT ? sifis_hazard_categories[hazard] : SIFIS_CATEGORY_COUNT;
+}
+
+/** Returns the hazard with the given name, or SIFIS_HAZARD_COUNT if unknown. */
+static inline enum sifis_hazard sifis_hazard_from_name(const char *name)
+{
+    size_t i;
+
+    for (i = 0; i < SIFIS_HAZARD_COUNT; i++) {
+        if (strcmp(sifis_hazard_names[i], name) == 0) {
+            return (enum sifis_hazard)i;
+        }
+    }
+    return SIFIS_HAZARD_COUNT;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SIFIS_HAZARDS_H */
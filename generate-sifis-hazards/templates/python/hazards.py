@@ -0,0 +1,118 @@
+"""SIFIS hazards ontology {{ ontology.version }}.
+
+Generated by generate-sifis-hazards, do not edit.
+"""
+
+from enum import Enum
+from typing import Dict, List, Optional
+
+#: IRI of the SIFIS hazards ontology.
+ONTOLOGY_IRI = "{{ ontology.iri | escape_python }}"
+
+#: Namespace of the SIFIS hazards ontology.
+ONTOLOGY_NAMESPACE = "{{ namespace | escape_python }}"
+
+#: Version of the SIFIS hazards ontology.
+ONTOLOGY_VERSION = "{{ ontology.version | escape_python }}"
+
+#: Lowest risk score admitted by the ontology.
+RISK_SCORE_MIN = {{ risk_score.min }}
+
+#: Highest risk score admitted by the ontology.
+RISK_SCORE_MAX = {{ risk_score.max }}
+
+_PREFIXES = ({% if prefix %}"{{ prefix | escape_python }}:", {% endif %}ONTOLOGY_NAMESPACE)
+
+
+def _local_name(name: str) -> str:
+    for prefix in _PREFIXES:
+        if name.startswith(prefix):
+            return name[len(prefix):]
+    return name
+
+
+class Category(str, Enum):
+    """Categories associated to an hazard."""
+{% for category in categories %}
+    #: {{ category.description | comment("    #: ") }}
+    {{ category.name | screaming_snake_case }} = "{{ category.name | escape_python }}"
+{%- endfor %}
+
+    def __str__(self) -> str:
+        return self.value
+
+    @classmethod
+    def from_name(cls, name: str) -> Optional["Category"]:
+        """Returns the category with the given name, compact IRI or full IRI."""
+        try:
+            return cls(_local_name(name))
+        except ValueError:
+            return None
+
+    @property
+    def description(self) -> str:
+        """Description of the category."""
+        return _CATEGORY_DESCRIPTIONS[self]
+
+    @property
+    def iri(self) -> str:
+        """Full IRI of the category."""
+        return ONTOLOGY_NAMESPACE + self.value
+
+    @property
+    def hazards(self) -> List["Hazard"]:
+        """Hazards belonging to the category."""
+        return [hazard for hazard in Hazard if hazard.category is self]
+
+
+class Hazard(str, Enum):
+    """Hazards type."""
+{% for hazard in hazards %}
+    #: {{ hazard.description | comment("    #: ") }}
+    {{ hazard.name | screaming_snake_case }} = "{{ hazard.name | escape_python }}"
+{%- endfor %}
+
+    def __str__(self) -> str:
+        return self.value
+
+    @classmethod
+    def from_name(cls, name: str) -> Optional["Hazard"]:
+        """Returns the hazard with the given name, compact IRI or full IRI."""
+        try:
+            return cls(_local_name(name))
+        except ValueError:
+            return None
+
+    @property
+    def description(self) -> str:
+        """Description of the hazard."""
+        return _HAZARD_DESCRIPTIONS[self]
+
+    @property
+    def category(self) -> Category:
+        """Category of the hazard."""
+        return _HAZARD_CATEGORIES[self]
+
+    @property
+    def iri(self) -> str:
+        """Full IRI of the hazard."""
+        return ONTOLOGY_NAMESPACE + self.value
+
+
+_CATEGORY_DESCRIPTIONS: Dict[Category, str] = {
+{%- for category in categories %}
+    Category.{{ category.name | screaming_snake_case }}: "{{ category.description | escape_python }}",
+{%- endfor %}
+}
+
+_HAZARD_DESCRIPTIONS: Dict[Hazard, str] = {
+{%- for hazard in hazards %}
+    Hazard.{{ hazard.name | screaming_snake_case }}: "{{ hazard.description | escape_python }}",
+{%- endfor %}
+}
+
+_HAZARD_CATEGORIES: Dict[Hazard, Category] = {
+{%- for hazard in hazards %}
+    Hazard.{{ hazard.name | screaming_snake_case }}: Category.{{ hazard.category | screaming_snake_case }},
+{%- endfor %}
+}
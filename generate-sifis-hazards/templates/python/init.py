@@ -0,0 +1,24 @@
+"""Hazards of the SIFIS hazards ontology {{ ontology.version }}.
+
+Generated by generate-sifis-hazards, do not edit.
+"""
+
+from .hazards import (
+    ONTOLOGY_IRI,
+    ONTOLOGY_NAMESPACE,
+    ONTOLOGY_VERSION,
+    RISK_SCORE_MAX,
+    RISK_SCORE_MIN,
+    Category,
+    Hazard,
+)
+
+__all__ = [
+    "ONTOLOGY_IRI",
+    "ONTOLOGY_NAMESPACE",
+    "ONTOLOGY_VERSION",
+    "RISK_SCORE_MAX",
+    "RISK_SCORE_MIN",
+    "Category",
+    "Hazard",
+]
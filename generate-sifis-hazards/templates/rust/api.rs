@@ -1,106 +1,864 @@
-#![allow(clippy::enum_variant_names)]
+use std::fmt;
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+use std::str::FromStr;
 
-use crate::thing::Hazard as ThingHazard;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+{%- if thing_hazard %}
+
+// The sifis crate parses Things with serde, so the conversions from their
+// hazards are only available with it.
+{%- if not for_include %}
+#[cfg(feature = "serde")]
+{%- endif %}
+use {{ thing_hazard }} as ThingHazard;
+{%- endif %}
+
+/// IRI of the SIFIS hazards ontology.
+pub const ONTOLOGY_IRI: &str = "{{ ontology.iri | escape_rust }}";
+
+/// Namespace of the SIFIS hazards ontology.
+pub const ONTOLOGY_NAMESPACE: &str = "{{ namespace | escape_rust }}";
+
+/// Title of the SIFIS hazards ontology.
+pub const ONTOLOGY_TITLE: &str = "{{ ontology.title | escape_rust }}";
+
+/// Version of the SIFIS hazards ontology this code has been generated from.
+pub const ONTOLOGY_VERSION: &str = "{{ ontology.version | escape_rust }}";
+
+/// Last modification date of the SIFIS hazards ontology.
+pub const ONTOLOGY_MODIFIED: &str = "{{ ontology.modified | escape_rust }}";
+
+/// Lowest risk score admitted by the ontology.
+pub const RISK_SCORE_MIN: u8 = {{ risk_score.min }};
+
+/// Highest risk score admitted by the ontology.
+pub const RISK_SCORE_MAX: u8 = {{ risk_score.max }};
+
+/// Prefixes of the ontology terms: the compact IRI prefix declared by the
+/// ontology, if any, and the namespace.
+const PREFIXES: &[&str] = &[
+    {%- if prefix %}
+    "{{ prefix | escape_rust }}:",
+    {%- endif %}
+    ONTOLOGY_NAMESPACE,
+];
+
+/// Returns the local name of an ontology term, which can be written as a
+/// plain name, as a compact IRI or as a full IRI.
+fn local_name(s: &str) -> &str {
+    PREFIXES
+        .iter()
+        .find_map(|prefix| s.strip_prefix(prefix))
+        .unwrap_or(s)
+}
+
+/// Texts of an ontology annotation, as `(language, text)` pairs.
+type LocalizedTexts = &'static [(&'static str, &'static str)];
+
+/// Picks the text in the requested language, falling back to its primary
+/// language subtag, then to English, to untagged texts and finally to the
+/// first available text.
+fn localized(texts: LocalizedTexts, lang: &str) -> Option<&'static str> {
+    let primary = lang.split('-').next().unwrap_or(lang);
+    [lang, primary, "en", ""]
+        .iter()
+        .find_map(|l| texts.iter().find(|(t, _)| t.eq_ignore_ascii_case(l)))
+        .or_else(|| texts.first())
+        .map(|(_, text)| *text)
+}
+
+/// Error returned when a string does not identify any `Hazard`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseHazardError(pub String);
+
+impl fmt::Display for ParseHazardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown hazard `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseHazardError {}
+
+/// Error returned when a string does not identify any `Category`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseCategoryError(pub String);
+
+impl fmt::Display for ParseCategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown category `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseCategoryError {}
 
 /// Hazards type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(clippy::enum_variant_names)]
 pub enum Hazard {
-{%- for hazard in hazards -%}
-    /// {{ hazard.description }}
+{%- for hazard in hazards %}
+{{ hazard.description | wrap_comment("    /// ", 100) }}
     {{ hazard.name }},
-{%- endfor -%}
+{%- endfor %}
 }
 
-impl ToString for Hazard {
-    fn to_string(&self) -> String {
-        match self {
-            {%- for hazard in hazards -%}
-                Self::{{ hazard.name }} => "{{ hazard.name }}",
-            {%- endfor -%}
-        }.to_owned()
+impl fmt::Display for Hazard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Hazard {
+    type Err = ParseHazardError;
+
+    /// Parses a `Hazard` from its name, its compact IRI or its full IRI.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match local_name(s) {
+            {%- for hazard in hazards %}
+            "{{ hazard.name | escape_rust }}" => Ok(Self::{{ hazard.name }}),
+            {%- endfor %}
+            _ => Err(ParseHazardError(s.to_owned())),
+        }
+    }
+}
+
+{%- if thing_hazard %}
+{%- if not for_include %}
+#[cfg(feature = "serde")]
+{%- endif %}
+impl TryFrom<&ThingHazard> for Hazard {
+    type Error = ParseHazardError;
+
+    /// Resolves the `@id` of a Thing hazard, falling back to its name.
+    fn try_from(thing_hazard: &ThingHazard) -> Result<Self, Self::Error> {
+        thing_hazard
+            .id
+            .parse()
+            .or_else(|_| thing_hazard.name.parse())
+    }
+}
+{%- endif %}
+
+#[cfg(feature = "serde")]
+impl Serialize for Hazard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Hazard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
     }
 }
 
 impl Hazard {
+    /// All the `Hazard` types.
+    pub const ALL: [Self; {{ hazards | length }}] = [
+        {%- for hazard in hazards %}
+        Self::{{ hazard.name }},
+        {%- endfor %}
+    ];
+
+    /// Returns an iterator over all the `Hazard` types.
+    pub fn iter() -> std::array::IntoIter<Self, {{ hazards | length }}> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the name of the hazard.
+    pub const fn name(self) -> &'static str {
+        match self {
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => "{{ hazard.name | escape_rust }}",
+            {%- endfor %}
+        }
+    }
+
+    /// Returns the bit of the hazard in a `HazardSet`.
+    ///
+    /// Bits are recorded in the lockfile of the generator, so they do not
+    /// change when other hazards are added to or removed from the ontology.
+    pub const fn bit(self) -> u32 {
+        match self {
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => {{ hazard_bits[hazard.name] }},
+            {%- endfor %}
+        }
+    }
+    {%- if thing_hazard %}
 
     /// Returns the `Hazard` type associated to a Thing.
     ///
     /// It returns `None` if any type has been found for the given
     /// Thing.
+    {%- if not for_include %}
+    #[cfg(feature = "serde")]
+    {%- endif %}
     pub fn has_hazard(thing_hazard: &ThingHazard) -> Option<Self> {
-        match thing_hazard.name.as_str() {
-            {%- for hazard in hazards -%}
-                "{{ hazard.name }}" => Some(Self::{{ hazard.name }}),
-            {%- endfor -%}
-            _ => None,
-        }
+        Self::try_from(thing_hazard).ok()
     }
+    {%- endif %}
 
     /// Returns the description associated to an `Hazard` type.
     pub fn get_description(&self) -> &str {
         match self {
-            {%- for hazard in hazards -%}
-                Self::{{ hazard.name }} => "{{ hazard.description }}",
-            {%- endfor -%}
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => "{{ hazard.description | escape_rust }}",
+            {%- endfor %}
+        }
+    }
+
+    fn descriptions(&self) -> LocalizedTexts {
+        match self {
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => &[
+                {%- for text in hazard.descriptions %}
+                ("{{ text.lang | escape_rust }}", "{{ text.value | escape_rust }}"),
+                {%- endfor %}
+            ],
+            {%- endfor %}
+        }
+    }
+
+    fn labels(&self) -> LocalizedTexts {
+        match self {
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => &[
+                {%- for text in hazard.labels %}
+                ("{{ text.lang | escape_rust }}", "{{ text.value | escape_rust }}"),
+                {%- else %}
+                ("", "{{ hazard.name | escape_rust }}"),
+                {%- endfor %}
+            ],
+            {%- endfor %}
         }
     }
 
+    fn comments(&self) -> LocalizedTexts {
+        match self {
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => &[
+                {%- for text in hazard.comments %}
+                ("{{ text.lang | escape_rust }}", "{{ text.value | escape_rust }}"),
+                {%- endfor %}
+            ],
+            {%- endfor %}
+        }
+    }
+
+    /// Returns the description in the given language.
+    ///
+    /// It falls back to the primary language subtag, then to English
+    /// and finally to any available description.
+    pub fn get_description_for(&self, lang: &str) -> &str {
+        localized(self.descriptions(), lang).unwrap_or_else(|| self.get_description())
+    }
+
+    /// Returns the label in English.
+    pub fn label(&self) -> &str {
+        self.label_for("en")
+    }
+
+    /// Returns the label in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn label_for(&self, lang: &str) -> &str {
+        localized(self.labels(), lang).unwrap_or_default()
+    }
+
+    /// Returns the comment in English, if any.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment_for("en")
+    }
+
+    /// Returns the comment in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn comment_for(&self, lang: &str) -> Option<&str> {
+        localized(self.comments(), lang)
+    }
+
+    /// Returns the IRI of the `Hazard`.
+    pub fn iri(&self) -> &'static str {
+        match self {
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => "{{ hazard.iri | escape_rust }}",
+            {%- endfor %}
+        }
+    }
+
+    /// Returns whether the ontology annotates the `Hazard` with a risk score.
+    pub fn has_risk_score(&self) -> bool {
+        matches!(
+            self,
+            {%- for hazard in hazards if hazard.has_risk_score %}
+            {% if not loop.first %}| {% endif %}Self::{{ hazard.name }}
+            {%- else %}
+            _ if false
+            {%- endfor %}
+        )
+    }
+
+    /// Returns the default risk score defined by the ontology, if any.
+    pub fn default_risk_score(&self) -> Option<u8> {
+        {%- for hazard in hazards if hazard.default_risk_score != none %}
+        {%- if loop.first %}
+        match self {
+        {%- endif %}
+            Self::{{ hazard.name }} => Some({{ hazard.default_risk_score }}),
+        {%- if loop.last %}
+            {%- if loop.length < hazards | length %}
+            _ => None,
+            {%- endif %}
+        }
+        {%- endif %}
+        {%- else %}
+        None
+        {%- endfor %}
+    }
+
     /// Returns the `Category`s associated to an `Hazard`.
     pub fn has_category(&self) -> Category {
         match self {
-            {%- for hazard in hazards -%}
-                Self::{{ hazard.name }} => Category::{{ hazard.category }},
-            {%- endfor -%}
+            {%- for hazard in hazards %}
+            Self::{{ hazard.name }} => Category::{{ hazard.category }},
+            {%- endfor %}
         }
     }
 
     /// Returns all `Hazard` types as immutable strings.
     pub fn all_hazards() -> &'static [&'static str] {
         &[
-            {%- for hazard in hazards -%}
-                "{{ hazard.name }}",
-            {%- endfor -%}
+            {%- for hazard in hazards %}
+            "{{ hazard.name | escape_rust }}",
+            {%- endfor %}
         ]
     }
 }
 
 /// Categories associated to an hazard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Category {
-{%- for category in categories -%}
-    /// {{ category.description }}
+{%- for category in categories %}
+{{ category.description | wrap_comment("    /// ", 100) }}
     {{ category.name }},
-{%- endfor -%}
+{%- endfor %}
 }
 
-impl ToString for Category {
-    fn to_string(&self) -> String {
-        match self {
-            {%- for category in categories -%}
-                Self::{{ category.name }} => "{{ category.name }}",
-            {%- endfor -%}
-        }.to_owned()
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            {%- for category in categories %}
+            Self::{{ category.name }} => "{{ category.name | escape_rust }}",
+            {%- endfor %}
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for Category {
+    type Err = ParseCategoryError;
+
+    /// Parses a `Category` from its name, its compact IRI or its full IRI.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match local_name(s) {
+            {%- for category in categories %}
+            "{{ category.name | escape_rust }}" => Ok(Self::{{ category.name }}),
+            {%- endfor %}
+            _ => Err(ParseCategoryError(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Category {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Category {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
     }
 }
 
 impl Category {
+    /// All the `Category` types.
+    pub const ALL: [Self; {{ categories | length }}] = [
+        {%- for category in categories %}
+        Self::{{ category.name }},
+        {%- endfor %}
+    ];
+
+    /// Returns an iterator over all the `Category` types.
+    pub fn iter() -> std::array::IntoIter<Self, {{ categories | length }}> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the description associated to a `Category` type.
+    pub fn get_description(&self) -> &str {
+        match self {
+            {%- for category in categories %}
+            Self::{{ category.name }} => "{{ category.description | escape_rust }}",
+            {%- endfor %}
+        }
+    }
+
+    fn descriptions(&self) -> LocalizedTexts {
+        match self {
+            {%- for category in categories %}
+            Self::{{ category.name }} => &[
+                {%- for text in category.descriptions %}
+                ("{{ text.lang | escape_rust }}", "{{ text.value | escape_rust }}"),
+                {%- endfor %}
+            ],
+            {%- endfor %}
+        }
+    }
+
+    fn labels(&self) -> LocalizedTexts {
+        match self {
+            {%- for category in categories %}
+            Self::{{ category.name }} => &[
+                {%- for text in category.labels %}
+                ("{{ text.lang | escape_rust }}", "{{ text.value | escape_rust }}"),
+                {%- else %}
+                ("", "{{ category.name | escape_rust }}"),
+                {%- endfor %}
+            ],
+            {%- endfor %}
+        }
+    }
+
+    fn comments(&self) -> LocalizedTexts {
+        match self {
+            {%- for category in categories %}
+            Self::{{ category.name }} => &[
+                {%- for text in category.comments %}
+                ("{{ text.lang | escape_rust }}", "{{ text.value | escape_rust }}"),
+                {%- endfor %}
+            ],
+            {%- endfor %}
+        }
+    }
+
+    /// Returns the description in the given language.
+    ///
+    /// It falls back to the primary language subtag, then to English
+    /// and finally to any available description.
+    pub fn get_description_for(&self, lang: &str) -> &str {
+        localized(self.descriptions(), lang).unwrap_or_else(|| self.get_description())
+    }
+
+    /// Returns the label in English.
+    pub fn label(&self) -> &str {
+        self.label_for("en")
+    }
+
+    /// Returns the label in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn label_for(&self, lang: &str) -> &str {
+        localized(self.labels(), lang).unwrap_or_default()
+    }
+
+    /// Returns the comment in English, if any.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment_for("en")
+    }
+
+    /// Returns the comment in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn comment_for(&self, lang: &str) -> Option<&str> {
+        localized(self.comments(), lang)
+    }
+
+    /// Returns the IRI of the `Category`.
+    pub fn iri(&self) -> &'static str {
+        match self {
+            {%- for category in categories %}
+            Self::{{ category.name }} => "{{ category.iri | escape_rust }}",
+            {%- endfor %}
+        }
+    }
+
+    /// Returns the `HazardSet` of the `Hazard`s associated to a `Category`.
+    pub fn hazard_set(&self) -> HazardSet {
+        self.all_hazards().iter().copied().collect()
+    }
+
     /// Returns all `Hazard`s associated to a `Category`.
     pub fn all_hazards(&self) -> &[Hazard] {
         match self {
-            {%- for category in categories -%}
-                Self::{{ category.name }} => &[
-                    {%- for hazard in category.hazards -%}
-                        Hazard::{{ hazard }},
-                    {%- endfor -%}
-                ],
-            {%- endfor -%}
+            {%- for category in categories %}
+            Self::{{ category.name }} => &[
+                {%- for hazard in category.hazards %}
+                Hazard::{{ hazard }},
+                {%- endfor %}
+            ],
+            {%- endfor %}
         }
     }
 
     /// Returns all `Category` as immutable strings.
     pub fn all_categories() -> &'static [&'static str] {
         &[
-            {%- for category in categories -%}
-                "{{ category.name }}",
-            {%- endfor -%}
+            {%- for category in categories %}
+            "{{ category.name | escape_rust }}",
+            {%- endfor %}
         ]
     }
 }
+
+/// Set of `Hazard`s, stored as the bits of a `u64`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HazardSet(u64);
+
+impl HazardSet {
+    /// The empty set.
+    pub const EMPTY: Self = Self(0);
+
+    /// The set of all the `Hazard`s.
+    pub const ALL: Self = {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < Hazard::ALL.len() {
+            bits |= 1 << Hazard::ALL[i].bit();
+            i += 1;
+        }
+        Self(bits)
+    };
+
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// Creates a set containing a single `Hazard`.
+    pub const fn from_hazard(hazard: Hazard) -> Self {
+        Self(1 << hazard.bit())
+    }
+
+    /// Creates a set from its bits.
+    ///
+    /// It returns `None` if any bit does not belong to a `Hazard`.
+    pub const fn from_bits(bits: u64) -> Option<Self> {
+        if bits & !Self::ALL.0 == 0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Creates a set from its bits, ignoring the ones which do not belong
+    /// to a `Hazard`.
+    pub const fn from_bits_truncate(bits: u64) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    /// Returns the bits of the set, to be stored as a `u64`.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Creates a set from the names of its `Hazard`s.
+    pub fn from_names<'a>(
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, ParseHazardError> {
+        names.into_iter().map(str::parse::<Hazard>).collect()
+    }
+
+    /// Returns the names of the `Hazard`s of the set.
+    pub fn names(self) -> Vec<&'static str> {
+        self.iter().map(|hazard| hazard.name()).collect()
+    }
+
+    /// Returns whether the set contains no `Hazard`.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the number of `Hazard`s of the set.
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns whether the set contains a `Hazard`.
+    pub const fn contains(self, hazard: Hazard) -> bool {
+        self.0 & Self::from_hazard(hazard).0 != 0
+    }
+
+    /// Adds a `Hazard` to the set, returning whether it was not present.
+    pub fn insert(&mut self, hazard: Hazard) -> bool {
+        let inserted = !self.contains(hazard);
+        self.0 |= Self::from_hazard(hazard).0;
+        inserted
+    }
+
+    /// Removes a `Hazard` from the set, returning whether it was present.
+    pub fn remove(&mut self, hazard: Hazard) -> bool {
+        let removed = self.contains(hazard);
+        self.0 &= !Self::from_hazard(hazard).0;
+        removed
+    }
+
+    /// Returns the `Hazard`s in either set.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the `Hazard`s in both sets.
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Returns the `Hazard`s in this set but not in the other one.
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns the `Hazard`s in only one of the sets.
+    pub const fn symmetric_difference(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+
+    /// Returns the `Hazard`s not in the set.
+    pub const fn complement(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+
+    /// Returns whether all the `Hazard`s of the set are in the other one.
+    pub const fn is_subset(self, other: Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Returns whether all the `Hazard`s of the other set are in this one.
+    pub const fn is_superset(self, other: Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns whether the sets have no `Hazard` in common.
+    pub const fn is_disjoint(self, other: Self) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    /// Returns an iterator over the `Hazard`s of the set.
+    pub fn iter(self) -> impl Iterator<Item = Hazard> {
+        Hazard::iter().filter(move |hazard| self.contains(*hazard))
+    }
+}
+
+impl fmt::Debug for HazardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<Hazard> for HazardSet {
+    fn from(hazard: Hazard) -> Self {
+        Self::from_hazard(hazard)
+    }
+}
+
+impl From<HazardSet> for u64 {
+    fn from(set: HazardSet) -> Self {
+        set.bits()
+    }
+}
+
+impl FromIterator<Hazard> for HazardSet {
+    fn from_iter<I: IntoIterator<Item = Hazard>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Hazard> for HazardSet {
+    fn extend<I: IntoIterator<Item = Hazard>>(&mut self, iter: I) {
+        for hazard in iter {
+            self.insert(hazard);
+        }
+    }
+}
+
+impl BitOr for HazardSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for HazardSet {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.union(other);
+    }
+}
+
+impl BitAnd for HazardSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl BitAndAssign for HazardSet {
+    fn bitand_assign(&mut self, other: Self) {
+        *self = self.intersection(other);
+    }
+}
+
+impl BitXor for HazardSet {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        self.symmetric_difference(other)
+    }
+}
+
+impl BitXorAssign for HazardSet {
+    fn bitxor_assign(&mut self, other: Self) {
+        *self = self.symmetric_difference(other);
+    }
+}
+
+impl Sub for HazardSet {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+impl SubAssign for HazardSet {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.difference(other);
+    }
+}
+
+impl Not for HazardSet {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+/// Serialized as the list of the names of its `Hazard`s.
+#[cfg(feature = "serde")]
+impl Serialize for HazardSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for HazardSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Hazard>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+{%- if not for_include %}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hazard() {
+        for hazard in Hazard::iter() {
+            assert_eq!(hazard.to_string(), hazard.name());
+            assert_eq!(hazard.name().parse(), Ok(hazard));
+            for prefix in PREFIXES {
+                assert_eq!(format!("{}{}", prefix, hazard).parse(), Ok(hazard));
+            }
+            assert_eq!(hazard.iri().parse(), Ok(hazard));
+            assert_eq!(hazard.label_for("zz-ZZ"), hazard.label());
+            assert_eq!(hazard.comment_for("zz-ZZ"), hazard.comment());
+            assert_eq!(
+                hazard.get_description_for("zz-ZZ"),
+                hazard.get_description_for("en")
+            );
+            assert!(hazard.has_category().hazard_set().contains(hazard));
+        }
+        assert_eq!("".parse::<Hazard>(), Err(ParseHazardError(String::new())));
+
+        for category in Category::iter() {
+            assert_eq!(category.to_string().parse(), Ok(category));
+            assert_eq!(category.iri().parse(), Ok(category));
+            assert_eq!(category.label_for("zz-ZZ"), category.label());
+        }
+        assert!("".parse::<Category>().is_err());
+    }
+    {%- if thing_hazard %}
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn thing_hazard() {
+        let hazard = Hazard::ALL[0];
+        let thing_hazard = |id: &str, name: &str| -> ThingHazard {
+            serde_json::from_value(serde_json::json!({
+                "@id": id,
+                "name": name,
+                "description": hazard.get_description(),
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(Hazard::try_from(&thing_hazard(hazard.iri(), "")), Ok(hazard));
+        assert_eq!(Hazard::has_hazard(&thing_hazard("", hazard.name())), Some(hazard));
+        assert_eq!(Hazard::has_hazard(&thing_hazard("", "")), None);
+
+        let json = serde_json::to_string(&hazard).unwrap();
+        assert_eq!(json, format!("\"{}\"", hazard));
+        assert_eq!(serde_json::from_str::<Hazard>(&json).unwrap(), hazard);
+        let compact = serde_json::json!(format!("{}{}", PREFIXES[0], hazard));
+        assert_eq!(serde_json::from_value::<Hazard>(compact).unwrap(), hazard);
+    }
+    {%- endif %}
+
+    #[test]
+    fn hazard_set() {
+        let first = Hazard::ALL[0];
+        let last = Hazard::ALL[Hazard::ALL.len() - 1];
+
+        let mut set = HazardSet::from(first);
+        assert!(set.insert(last));
+        assert!(!set.insert(last));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [first, last]);
+        assert_eq!(HazardSet::from_bits(set.bits()), Some(set));
+        assert_eq!(HazardSet::from_names(set.names()), Ok(set));
+        assert_eq!(
+            HazardSet::from_names(["Unknown"]),
+            Err(ParseHazardError("Unknown".to_owned()))
+        );
+
+        assert_eq!(set | !set, HazardSet::ALL);
+        assert_eq!(set & !set, HazardSet::EMPTY);
+        assert_eq!(set - HazardSet::from(first), HazardSet::from(last));
+        assert!(HazardSet::from(first).is_subset(set));
+        assert!(set.is_disjoint(!set));
+        assert_eq!(HazardSet::ALL.len(), Hazard::ALL.len());
+        assert_eq!(HazardSet::from_bits_truncate(u64::MAX), HazardSet::ALL);
+
+        let categories = Category::iter().fold(HazardSet::EMPTY, |set, category| {
+            assert!(set.is_disjoint(category.hazard_set()));
+            set | category.hazard_set()
+        });
+        assert_eq!(categories, HazardSet::ALL);
+    }
+}
+{%- endif %}
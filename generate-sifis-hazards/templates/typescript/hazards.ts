@@ -0,0 +1,126 @@
+/**
+ * SIFIS hazards ontology {{ ontology.version }}.
+ *
+ * Generated by generate-sifis-hazards, do not edit.
+ */
+
+/** IRI of the SIFIS hazards ontology. */
+export const ONTOLOGY_IRI = "{{ ontology.iri | escape_json }}";
+
+/** Namespace of the SIFIS hazards ontology. */
+export const ONTOLOGY_NAMESPACE = "{{ namespace | escape_json }}";
+
+/** Version of the SIFIS hazards ontology. */
+export const ONTOLOGY_VERSION = "{{ ontology.version | escape_json }}";
+
+/** Lowest risk score admitted by the ontology. */
+export const RISK_SCORE_MIN = {{ risk_score.min }};
+
+/** Highest risk score admitted by the ontology. */
+export const RISK_SCORE_MAX = {{ risk_score.max }};
+
+const PREFIXES = [{% if prefix %}"{{ prefix | escape_json }}:", {% endif %}ONTOLOGY_NAMESPACE];
+
+/** All the hazard categories. */
+export const CATEGORIES = [
+{%- for category in categories %}
+  "{{ category.name | escape_json }}",
+{%- endfor %}
+] as const;
+
+/** Categories associated to an hazard. */
+export type Category = (typeof CATEGORIES)[number];
+
+/** All the hazards. */
+export const HAZARDS = [
+{%- for hazard in hazards %}
+  "{{ hazard.name | escape_json }}",
+{%- endfor %}
+] as const;
+
+/** Hazards type. */
+export type Hazard = (typeof HAZARDS)[number];
+
+/** Information about a category. */
+export interface CategoryInfo {
+  readonly name: Category;
+  readonly iri: string;
+  readonly description: string;
+}
+
+/** Information about a hazard. */
+export interface HazardInfo {
+  readonly name: Hazard;
+  readonly iri: string;
+  readonly description: string;
+  readonly category: Category;
+}
+
+/** Information about each category. */
+export const CATEGORY_INFO: Readonly<Record<Category, CategoryInfo>> = {
+{%- for category in categories %}
+  {{ category.name }}: {
+    name: "{{ category.name | escape_json }}",
+    iri: "{{ category.iri | escape_json }}",
+    description: "{{ category.description | escape_json }}",
+  },
+{%- endfor %}
+};
+
+/** Information about each hazard. */
+export const HAZARD_INFO: Readonly<Record<Hazard, HazardInfo>> = {
+{%- for hazard in hazards %}
+  {{ hazard.name }}: {
+    name: "{{ hazard.name | escape_json }}",
+    iri: "{{ hazard.iri | escape_json }}",
+    description: "{{ hazard.description | escape_json }}",
+    category: "{{ hazard.category | escape_json }}",
+  },
+{%- endfor %}
+};
+
+function localName(name: string): string {
+  for (const prefix of PREFIXES) {
+    if (name.startsWith(prefix)) {
+      return name.slice(prefix.length);
+    }
+  }
+  return name;
+}
+
+/** Returns whether a string is a category name. */
+export function isCategory(value: string): value is Category {
+  return (CATEGORIES as readonly string[]).includes(value);
+}
+
+/** Returns whether a string is a hazard name. */
+export function isHazard(value: string): value is Hazard {
+  return (HAZARDS as readonly string[]).includes(value);
+}
+
+/** Returns the category with the given name, compact IRI or full IRI. */
+export function parseCategory(name: string): Category | undefined {
+  const category = localName(name);
+  return isCategory(category) ? category : undefined;
+}
+
+/** Returns the hazard with the given name, compact IRI or full IRI. */
+export function parseHazard(name: string): Hazard | undefined {
+  const hazard = localName(name);
+  return isHazard(hazard) ? hazard : undefined;
+}
+
+/** Returns the category of a hazard. */
+export function hazardCategory(hazard: Hazard): Category {
+  return HAZARD_INFO[hazard].category;
+}
+
+/** Returns the description of a hazard. */
+export function hazardDescription(hazard: Hazard): string {
+  return HAZARD_INFO[hazard].description;
+}
+
+/** Returns the hazards belonging to a category. */
+export function categoryHazards(category: Category): Hazard[] {
+  return HAZARDS.filter((hazard) => HAZARD_INFO[hazard].category === category);
+}
@@ -0,0 +1,7 @@
+/**
+ * Hazards of the SIFIS hazards ontology {{ ontology.version }}.
+ *
+ * Generated by generate-sifis-hazards, do not edit.
+ */
+
+export * from "./hazards";
@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn ontology_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("ontology.jsonld")
+}
+
+// Runs the generator with a lockfile in `dir`, so the committed one is
+// never updated.
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_generate-sifis-hazards"))
+        .arg("--lockfile")
+        .arg(dir.join("ontology.lock"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn stdout_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let ontology = ontology_path();
+    let ontology = ontology.to_str().unwrap();
+
+    // The ontology path is not taken as the value of `--stdout`
+    let output = run(dir.path(), &["-t", "c", "--stdout", ontology]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("#ifndef SIFIS_HAZARDS_H"));
+
+    let output = run(dir.path(), &["-t", "python", "--stdout", ontology]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("The template generates more than one file"));
+
+    let output = run(
+        dir.path(),
+        &[
+            "-t",
+            "python",
+            "--stdout=sifis_hazards/hazards.py",
+            ontology,
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("class Hazard(str, Enum):"));
+
+    let output = run(
+        dir.path(),
+        &["-t", "python", "--stdout=hazards.py", ontology],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("hazards.py is not generated by the Python template"));
+
+    assert!(fs::read_dir(dir.path()).unwrap().next().is_none());
+}
+
+#[test]
+fn dry_run_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let ontology = ontology_path();
+    let output_path = dir.path().join("output");
+
+    let output = run(
+        dir.path(),
+        &[
+            "-t",
+            "c",
+            "--dry-run",
+            ontology.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{}\n{}\n",
+            output_path.join("include/sifis_hazards.h").display(),
+            dir.path().join("ontology.lock").display()
+        )
+    );
+    assert!(fs::read_dir(dir.path()).unwrap().next().is_none());
+}
+
+#[test]
+fn check_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let ontology = ontology_path();
+    let args = [
+        "-t",
+        "c",
+        ontology.to_str().unwrap(),
+        dir.path().to_str().unwrap(),
+    ];
+    let header = dir.path().join("include/sifis_hazards.h");
+    let check = || run(dir.path(), &[&args[..], &["--check"]].concat());
+
+    let output = check();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains(&format!("+++ {}\n", header.display())));
+    assert!(!header.exists());
+
+    let output = run(dir.path(), &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = check();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "");
+
+    fs::write(&header, "// Outdated\n").unwrap();
+    let output = check();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("-// Outdated\n"));
+    assert!(!stdout(&output).contains("ontology.lock"));
+
+    // A renamed hazard is reported along with its new bit
+    let lockfile = dir.path().join("ontology.lock");
+    let content = fs::read_to_string(&lockfile).unwrap();
+    fs::write(&lockfile, content.replace("FireHazard = 6", "Fire = 6")).unwrap();
+    let output = check();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("+FireHazard = 22\n"));
+    assert!(stderr(&output).contains("`FireHazard` got a new bit while `Fire` left the ontology"));
+}
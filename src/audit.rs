@@ -0,0 +1,428 @@
+//! Tamper-evident audit log of hazardous interactions.
+//!
+//! Each interaction involving a hazardous affordance is appended as a JSON
+//! line to a local file. Every entry contains the hash of the previous one,
+//! so changing, removing or reordering entries breaks the chain and can be
+//! detected with [`verify`].
+//!
+//! Removing the trailing entries leaves a valid chain, so truncation cannot
+//! be detected from the log alone: store the [`ChainHead`] returned by
+//! [`verify`] elsewhere and compare it with the later verifications.
+//!
+//! Each [`AuditLog`] caches the end of the chain, so a log must be opened
+//! once and its handle shared, for example by all the `ConsumedThing`s.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::ontology::Hazard;
+use crate::policy::{Decision, Interaction, Request};
+use crate::thing::RiskScore;
+
+/// Hash preceding the first entry of a log.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn sha256(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+// Writes a JSON value with the object keys sorted, so the encoding does not
+// depend on the order in which the keys have been inserted.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(value, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        value => out.push_str(&value.to_string()),
+    }
+}
+
+// Returns the SHA-256 digest of the canonical encoding of a JSON value.
+fn digest(value: &Value) -> String {
+    let mut encoded = String::new();
+    write_canonical(value, &mut encoded);
+    sha256(encoded.as_bytes())
+}
+
+/// Hazard involved in an audited interaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditedHazard {
+    /// Ontology hazard, `None` if the Thing hazard could not be resolved.
+    pub hazard: Option<Hazard>,
+    /// Name of the hazard as found in the Thing Description.
+    pub name: String,
+    /// Risk score of the hazard, `None` if missing.
+    pub risk_score: Option<RiskScore>,
+}
+
+/// Entry of the audit log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position of the entry in the log, starting from 0.
+    pub sequence: u64,
+    /// Seconds elapsed since the UNIX epoch.
+    pub timestamp: u64,
+    /// Identifier of the Thing.
+    pub thing: String,
+    /// Kind of interaction.
+    pub interaction: Interaction,
+    /// Name of the affordance.
+    pub affordance: String,
+    /// Hazards of the affordance.
+    pub hazards: Vec<AuditedHazard>,
+    /// Decision taken by the checks.
+    pub decision: Decision,
+    /// Whether the checks let the interaction proceed.
+    ///
+    /// The entry is written before the request is sent, so an allowed
+    /// interaction might still have failed.
+    pub allowed: bool,
+    /// SHA-256 digest of the JSON payload, if any.
+    pub payload_digest: Option<String>,
+    /// Hash of the previous entry.
+    pub previous_hash: String,
+    /// Hash of this entry.
+    pub hash: String,
+}
+
+impl AuditEntry {
+    // Computes the hash of the entry, covering every field but the hash.
+    fn compute_hash(&self) -> Result<String> {
+        let mut content = serde_json::to_value(self)?;
+        if let Value::Object(map) = &mut content {
+            map.remove("hash");
+        }
+        Ok(digest(&content))
+    }
+}
+
+/// Append-only audit log.
+///
+/// The log caches the sequence number and the hash of its last entry, so
+/// the same file must not be opened twice: share the handle instead.
+#[derive(Debug)]
+pub struct AuditLog {
+    file: File,
+    next_sequence: u64,
+    last_hash: String,
+}
+
+impl AuditLog {
+    /// Opens an audit log, creating it if it does not exist.
+    ///
+    /// The existing entries are verified, and new entries are chained to
+    /// the last one. A log which fails verification cannot be extended.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        let ChainHead { entries, last_hash } = verify(path).with_context(|| {
+            format!(
+                "Cannot append to the audit log {}, move it aside to keep it \
+                 as evidence and start a new log",
+                path.display()
+            )
+        })?;
+
+        Ok(Self {
+            file,
+            next_sequence: entries,
+            last_hash,
+        })
+    }
+
+    /// Appends the outcome of an interaction to the log.
+    pub fn append(
+        &mut self,
+        request: &Request,
+        decision: Decision,
+        allowed: bool,
+        payload: Option<&Value>,
+    ) -> Result<AuditEntry> {
+        let hazards = request
+            .hazards
+            .iter()
+            .map(|hazard| AuditedHazard {
+                hazard: Hazard::has_hazard(hazard),
+                name: hazard.name.clone(),
+                risk_score: hazard.risk_score(),
+            })
+            .collect();
+
+        let mut entry = AuditEntry {
+            sequence: self.next_sequence,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            thing: request.thing.id.clone(),
+            interaction: request.interaction,
+            affordance: request.affordance.to_owned(),
+            hazards,
+            decision,
+            allowed,
+            payload_digest: payload.map(digest),
+            previous_hash: self.last_hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash()?;
+
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        self.file.flush()?;
+
+        self.next_sequence += 1;
+        self.last_hash = entry.hash.clone();
+
+        Ok(entry)
+    }
+}
+
+/// Iterator over the entries of an audit log.
+pub struct AuditReader {
+    lines: Lines<BufReader<File>>,
+}
+
+impl AuditReader {
+    /// Opens an audit log for reading.
+    pub fn open(path: &Path) -> Result<Self> {
+        let lines = BufReader::new(File::open(path)?).lines();
+        Ok(Self { lines })
+    }
+}
+
+impl Iterator for AuditReader {
+    type Item = Result<AuditEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(e) => return Some(Err(e.into())),
+        };
+        Some(serde_json::from_str(&line).map_err(Into::into))
+    }
+}
+
+/// Reason why an audit log failed verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Tampering {
+    /// The entry content does not match its hash.
+    HashMismatch,
+    /// The entry is not chained to the previous one.
+    BrokenChain,
+    /// The entry is not in the expected position.
+    UnexpectedSequence(u64),
+}
+
+/// Error returned when an audit log has been tampered with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TamperedLog {
+    /// Line of the first invalid entry, starting from 1.
+    pub line: usize,
+    /// Why the entry is invalid.
+    pub reason: Tampering,
+}
+
+impl fmt::Display for TamperedLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.reason {
+            Tampering::HashMismatch => "content does not match its hash".to_owned(),
+            Tampering::BrokenChain => "not chained to the previous entry".to_owned(),
+            Tampering::UnexpectedSequence(sequence) => {
+                format!("unexpected sequence number {}", sequence)
+            }
+        };
+        write!(f, "Audit log tampered at line {}: {}", self.line, reason)
+    }
+}
+
+impl std::error::Error for TamperedLog {}
+
+/// End of a verified hash chain.
+///
+/// A log truncated to fewer entries than a previously stored head has lost
+/// entries, and a log whose entry at that position has a different hash has
+/// been rewritten.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainHead {
+    /// Number of entries in the log.
+    pub entries: u64,
+    /// Hash of the last entry, the genesis hash for an empty log.
+    pub last_hash: String,
+}
+
+/// Verifies the hash chain of an audit log.
+///
+/// Returns the end of the chain, or a [`TamperedLog`] error pointing to the
+/// first invalid entry.
+pub fn verify(path: &Path) -> Result<ChainHead> {
+    let mut previous_hash = GENESIS_HASH.to_owned();
+    let mut count = 0;
+
+    for (i, entry) in AuditReader::open(path)?.enumerate() {
+        let entry = entry.with_context(|| format!("Invalid audit log entry at line {}", i + 1))?;
+        let reason = if entry.sequence != count {
+            Some(Tampering::UnexpectedSequence(entry.sequence))
+        } else if entry.previous_hash != previous_hash {
+            Some(Tampering::BrokenChain)
+        } else if entry.compute_hash()? != entry.hash {
+            Some(Tampering::HashMismatch)
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            return Err(TamperedLog {
+                line: i + 1,
+                reason,
+            }
+            .into());
+        }
+
+        previous_hash = entry.hash;
+        count += 1;
+    }
+
+    Ok(ChainHead {
+        entries: count,
+        last_hash: previous_hash,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::thing::{Hazard as ThingHazard, Thing};
+
+    #[test]
+    fn hash_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+
+        let thing: Thing = serde_json::from_str(
+            r#"{
+                "@context": "https://www.w3.org/2019/wot/td/v1",
+                "id": "urn:dev:ops:my-oven-1234",
+                "title": "MyOven",
+                "securityDefinitions": {}
+            }"#,
+        )
+        .unwrap();
+        let hazards: Vec<ThingHazard> = serde_json::from_str(
+            r#"[{
+                "@id": "sho:FireHazard",
+                "name": "FireHazard",
+                "description": "The execution may cause fire",
+                "riskScore": 6
+            }]"#,
+        )
+        .unwrap();
+        let request = Request {
+            thing: &thing,
+            interaction: Interaction::InvokeAction,
+            affordance: "bake",
+            hazards: &hazards,
+        };
+
+        let payload = serde_json::json!({"temperature": 200});
+        let mut log = AuditLog::open(&path).unwrap();
+        log.append(&request, Decision::Allow, true, Some(&payload))
+            .unwrap();
+        log.append(&request, Decision::Deny, false, None).unwrap();
+        drop(log);
+
+        // Reopening the log continues the chain
+        let mut log = AuditLog::open(&path).unwrap();
+        let entry = log.append(&request, Decision::Ask, true, None).unwrap();
+        assert_eq!(entry.sequence, 2);
+        let head = verify(&path).unwrap();
+        assert_eq!(head.entries, 3);
+        assert_eq!(head.last_hash, entry.hash);
+
+        let entries: Vec<_> = AuditReader::open(&path)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(entries[0].hazards[0].hazard, Some(Hazard::FireHazard));
+        assert_eq!(entries[0].hazards[0].risk_score, RiskScore::new(6));
+        assert!(entries[0].payload_digest.is_some());
+        assert_eq!(entries[1].previous_hash, entries[0].hash);
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = content.lines().collect();
+
+        // Dropping the last entry leaves a valid chain, only the stored head
+        // reveals it
+        fs::write(&path, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        let truncated = verify(&path).unwrap();
+        assert_eq!(truncated.entries, 2);
+        assert_eq!(truncated.last_hash, entries[1].hash);
+        assert_ne!(truncated, head);
+
+        fs::write(
+            &path,
+            content.replacen("\"allowed\":false", "\"allowed\":true", 1),
+        )
+        .unwrap();
+        let err = verify(&path).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<TamperedLog>(),
+            Some(&TamperedLog {
+                line: 2,
+                reason: Tampering::HashMismatch
+            })
+        );
+
+        fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        let err = verify(&path).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<TamperedLog>().map(|e| &e.reason),
+            Some(&Tampering::UnexpectedSequence(2))
+        );
+
+        // A truncated entry is reported instead of being chained to
+        fs::write(&path, format!("{}\n{}", lines[0], &lines[1][..20])).unwrap();
+        let err = format!("{:#}", AuditLog::open(&path).unwrap_err());
+        assert!(err.contains("move it aside"));
+        assert!(err.contains("Invalid audit log entry at line 2"));
+    }
+
+    #[test]
+    fn canonical_digest() {
+        let value = serde_json::json!({"b": [1, {"d": null, "c": "\""}], "a": true});
+        let mut encoded = String::new();
+        write_canonical(&value, &mut encoded);
+        assert_eq!(encoded, r#"{"a":true,"b":[1,{"c":"\"","d":null}]}"#);
+
+        let reordered = serde_json::json!({"a": true, "b": [1, {"c": "\"", "d": null}]});
+        assert_eq!(digest(&value), digest(&reordered));
+    }
+}
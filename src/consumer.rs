@@ -0,0 +1,388 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use reqwest::{blocking, Url};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::audit::AuditLog;
+use crate::manifest::{AppManifest, ConsentStore};
+use crate::policy::{Decision, Denied, Interaction, Policy, Request};
+use crate::thing::{Form, Hazard, Thing};
+
+/// Thing consumed through the hazard checks.
+///
+/// Every property write, action invocation and event subscription is
+/// checked against the app consent, if any, and then against the
+/// [`Policy`] before any request is sent to the Thing.
+/// The interactions involving hazards can be recorded in an [`AuditLog`].
+#[derive(Debug)]
+pub struct ConsumedThing {
+    thing: Thing,
+    policy: Policy,
+    consent: Option<(AppManifest, ConsentStore)>,
+    audit: Option<Arc<Mutex<AuditLog>>>,
+    client: blocking::Client,
+}
+
+impl ConsumedThing {
+    /// Consumes a Thing, allowing every interaction.
+    pub fn new(thing: Thing) -> Self {
+        Self {
+            thing,
+            policy: Policy::default(),
+            consent: None,
+            audit: None,
+            client: blocking::Client::new(),
+        }
+    }
+
+    /// Sets the policy the interactions are checked against.
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Blocks the interactions involving hazards not declared in the
+    /// app manifest or not granted by the user.
+    pub fn with_consent(mut self, manifest: AppManifest, store: ConsentStore) -> Self {
+        self.consent = Some((manifest, store));
+        self
+    }
+
+    /// Records the interactions involving hazards, whether they are
+    /// allowed or not.
+    ///
+    /// The same log can be shared by several Things, their entries are
+    /// appended to a single hash chain.
+    pub fn with_audit(mut self, log: Arc<Mutex<AuditLog>>) -> Self {
+        self.audit = Some(log);
+        self
+    }
+
+    /// Returns the consent store, if any, to grant or revoke hazards.
+    pub fn consent_mut(&mut self) -> Option<&mut ConsentStore> {
+        self.consent.as_mut().map(|(_, store)| store)
+    }
+
+    /// Returns the consumed Thing.
+    pub fn thing(&self) -> &Thing {
+        &self.thing
+    }
+
+    /// Returns the policy the interactions are checked against.
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Writes the value of a property.
+    pub fn write_property<T: Serialize>(&self, name: &str, value: T) -> Result<()> {
+        let property = self
+            .thing
+            .properties
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown property `{}`", name))?;
+        let value = serde_json::to_value(value)?;
+        let url = self.authorize(
+            Interaction::WriteProperty,
+            name,
+            property.hazards(),
+            &property.affordance.forms,
+            Some(&value),
+        )?;
+
+        self.client
+            .put(url)
+            .json(&value)
+            .send()?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Invokes an action, returning its output.
+    ///
+    /// It returns `Value::Null` if the action has no output.
+    pub fn invoke_action<T: Serialize>(&self, name: &str, input: Option<T>) -> Result<Value> {
+        let action = self
+            .thing
+            .actions
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown action `{}`", name))?;
+        let input = input.map(serde_json::to_value).transpose()?;
+        let url = self.authorize(
+            Interaction::InvokeAction,
+            name,
+            action.hazards(),
+            &action.affordance.forms,
+            input.as_ref(),
+        )?;
+
+        let mut request = self.client.post(url);
+        if let Some(input) = &input {
+            request = request.json(input);
+        }
+        let output = request.send()?.error_for_status()?.text()?;
+
+        if output.is_empty() {
+            Ok(Value::Null)
+        } else {
+            Ok(serde_json::from_str(&output)?)
+        }
+    }
+
+    /// Subscribes to an event.
+    pub fn subscribe_event(&self, name: &str) -> Result<Subscription> {
+        let event = self
+            .thing
+            .events
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown event `{}`", name))?;
+        let url = self.authorize(
+            Interaction::SubscribeEvent,
+            name,
+            event.hazards(),
+            &event.affordance.forms,
+            None,
+        )?;
+
+        Ok(Subscription {
+            client: self.client.clone(),
+            url,
+        })
+    }
+
+    // Checks the interaction against the consent and the policy
+    fn check(&self, request: &Request) -> Result<Decision> {
+        if let Some((manifest, store)) = &self.consent {
+            store.check(manifest, request).map_err(|e| *e)?;
+        }
+        Ok(self.policy.check(request)?)
+    }
+
+    // Checks and audits the interaction, then returns the endpoint of
+    // the affordance.
+    fn authorize(
+        &self,
+        interaction: Interaction,
+        affordance: &str,
+        hazards: &[Hazard],
+        forms: &[Form],
+        payload: Option<&Value>,
+    ) -> Result<Url> {
+        let request = Request {
+            thing: &self.thing,
+            interaction,
+            affordance,
+            hazards,
+        };
+        let outcome = self.check(&request);
+
+        if let Some(audit) = self.audit.as_ref().filter(|_| !hazards.is_empty()) {
+            let (decision, allowed) = match &outcome {
+                Ok(decision) => (*decision, true),
+                Err(e) => (
+                    e.downcast_ref::<Denied>()
+                        .map_or(Decision::Deny, |denied| denied.decision),
+                    false,
+                ),
+            };
+            audit
+                .lock()
+                .map_err(|_| anyhow!("Audit log lock poisoned"))?
+                .append(&request, decision, allowed, payload)?;
+        }
+        outcome?;
+
+        let form = forms
+            .first()
+            .ok_or_else(|| anyhow!("No forms available for `{}`", affordance))?;
+
+        let url = if self.thing.base.is_empty() {
+            Url::parse(&form.href)?
+        } else {
+            Url::parse(&self.thing.base)?.join(&form.href)?
+        };
+
+        Ok(url)
+    }
+}
+
+/// Subscription to an event.
+///
+/// Each iteration waits for the next event data, using long polling.
+#[derive(Debug)]
+pub struct Subscription {
+    client: blocking::Client,
+    url: Url,
+}
+
+impl Iterator for Subscription {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self
+            .client
+            .get(self.url.clone())
+            .send()
+            .and_then(|r| r.error_for_status())
+            .and_then(|r| r.json())
+            .map_err(Into::into);
+
+        Some(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+    use crate::audit::{verify, AuditReader};
+    use crate::ontology::{Category, Hazard as OntologyHazard};
+    use crate::policy::Matcher;
+
+    fn oven(base: &str) -> Thing {
+        serde_json::from_str(&format!(
+            r#"{{
+                "@context": "https://www.w3.org/2019/wot/td/v1",
+                "id": "urn:dev:ops:my-oven-1234",
+                "title": "MyOven",
+                "base": "{}",
+                "securityDefinitions": {{}},
+                "properties": {{
+                    "temperature": {{
+                        "type": "integer",
+                        "hazards": {{
+                            "@id": "sho:FireHazard",
+                            "name": "FireHazard",
+                            "description": "The execution may cause fire"
+                        }},
+                        "forms": [{{"href": "temperature"}}]
+                    }}
+                }},
+                "actions": {{
+                    "bake": {{
+                        "hazards": [
+                            {{
+                                "@id": "sho:FireHazard",
+                                "name": "FireHazard",
+                                "description": "The execution may cause fire"
+                            }},
+                            {{
+                                "@id": "sho:ElectricEnergyConsumption",
+                                "name": "ElectricEnergyConsumption",
+                                "description": "The execution enables a device that consumes electricity"
+                            }}
+                        ],
+                        "forms": [{{"href": "bake"}}]
+                    }}
+                }}
+            }}"#,
+            base
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn denied_before_sending() {
+        let thing = oven("http://192.0.2.1/");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+
+        let log = Arc::new(Mutex::new(AuditLog::open(&path).unwrap()));
+        let consumed = ConsumedThing::new(thing.clone())
+            .with_policy(
+                Policy::new(Decision::Allow).deny(Matcher::Hazard(OntologyHazard::FireHazard)),
+            )
+            .with_audit(log.clone());
+        let other = ConsumedThing::new(thing)
+            .with_policy(Policy::new(Decision::Deny))
+            .with_audit(log);
+
+        let err = consumed.invoke_action::<()>("bake", None).unwrap_err();
+        let denied = err.downcast_ref::<Denied>().unwrap();
+        assert_eq!(denied.affordance, "bake");
+        assert_eq!(denied.interaction, Interaction::InvokeAction);
+        assert_eq!(denied.hazards, ["FireHazard"]);
+
+        let err = consumed.write_property("temperature", 200).unwrap_err();
+        let denied = err.downcast_ref::<Denied>().unwrap();
+        assert_eq!(denied.affordance, "temperature");
+        assert_eq!(denied.interaction, Interaction::WriteProperty);
+        assert_eq!(denied.decision, Decision::Deny);
+
+        // Both Things append to the same hash chain
+        assert!(other.invoke_action::<()>("bake", None).is_err());
+        assert!(consumed.invoke_action::<()>("bake", None).is_err());
+        assert_eq!(verify(&path).unwrap().entries, 4);
+
+        let entries: Vec<_> = AuditReader::open(&path).unwrap().collect();
+        assert!(!entries[0].as_ref().unwrap().allowed);
+    }
+
+    #[test]
+    fn allowed_reaches_the_thing() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/", listener.local_addr().unwrap());
+
+        // Answers a single request, returning its request line and body
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let header = header.trim_end().to_ascii_lowercase();
+                if header.is_empty() {
+                    break;
+                }
+                if let Some(value) = header.strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let output = r#"{"baked":true}"#;
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                output.len(),
+                output
+            )
+            .unwrap();
+
+            (request_line, String::from_utf8(body).unwrap())
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let consumed = ConsumedThing::new(oven(&base))
+            .with_policy(
+                Policy::new(Decision::Deny)
+                    .allow(Matcher::Category(Category::Safety))
+                    .allow(Matcher::Category(Category::Financial)),
+            )
+            .with_audit(Arc::new(Mutex::new(AuditLog::open(&path).unwrap())));
+
+        let output = consumed
+            .invoke_action("bake", Some(serde_json::json!({"temperature": 200})))
+            .unwrap();
+        assert_eq!(output, serde_json::json!({"baked": true}));
+
+        let (request_line, body) = server.join().unwrap();
+        assert_eq!(request_line.trim_end(), "POST /bake HTTP/1.1");
+        assert_eq!(body, r#"{"temperature":200}"#);
+
+        let entries: Vec<_> = AuditReader::open(&path).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].as_ref().unwrap().allowed);
+    }
+}
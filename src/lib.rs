@@ -1,11 +1,41 @@
+#[cfg(feature = "serde")]
+pub mod audit;
+#[cfg(feature = "serde")]
+mod consumer;
+#[cfg(feature = "serde")]
 mod discovery;
-mod ontology;
-mod thing;
+#[cfg(feature = "serde")]
+pub mod manifest;
+// Generated by generate-sifis-hazards and formatted with prettyplease.
+#[rustfmt::skip]
+pub mod ontology;
+#[cfg(feature = "serde")]
+pub mod policy;
+#[cfg(feature = "serde")]
+pub mod risk;
+#[cfg(feature = "serde")]
+pub mod simulation;
+#[cfg(feature = "serde")]
+pub mod thing;
 
 pub mod error {
     pub use anyhow::{Error, Result};
+
+    #[cfg(feature = "serde")]
+    pub use crate::audit::TamperedLog;
+    #[cfg(feature = "serde")]
+    pub use crate::manifest::NotGranted;
+    #[cfg(feature = "serde")]
+    pub use crate::policy::Denied;
 }
 
+#[cfg(feature = "serde")]
+pub use consumer::*;
+#[cfg(feature = "serde")]
 pub use discovery::*;
 pub use ontology::*;
+#[cfg(feature = "serde")]
 pub use thing::*;
+
+// Both modules define a `Hazard`, the ontology one is the canonical type.
+pub use ontology::Hazard;
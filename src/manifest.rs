@@ -0,0 +1,352 @@
+//! App manifest and user consent.
+//!
+//! An [`AppManifest`] declares up front the hazards an app needs, while a
+//! [`ConsentStore`] records which of them the user granted.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::ontology::{Category, Hazard};
+use crate::policy::{Interaction, Request};
+
+/// Hazard requested by an app.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HazardRequest {
+    /// Requested hazard.
+    pub hazard: Hazard,
+    /// Reason why the app needs the hazard, shown to the user.
+    pub justification: String,
+}
+
+/// Category of hazards requested by an app.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryRequest {
+    /// Requested category, it stands for all of its hazards.
+    pub category: Category,
+    /// Reason why the app needs the category, shown to the user.
+    pub justification: String,
+}
+
+/// Hazards an app declares to need.
+///
+/// It can be written in TOML:
+///
+/// ```toml
+/// name = "oven-controller"
+///
+/// [[hazards]]
+/// hazard = "FireHazard"
+/// justification = "Turns the oven on at the scheduled time"
+///
+/// [[categories]]
+/// category = "Financial"
+/// justification = "Monitors the energy consumption"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppManifest {
+    /// Name identifying the app.
+    pub name: String,
+    /// Description of the app, shown to the user.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Hazards requested one by one.
+    #[serde(default)]
+    pub hazards: Vec<HazardRequest>,
+    /// Categories requested as a whole.
+    #[serde(default)]
+    pub categories: Vec<CategoryRequest>,
+}
+
+impl AppManifest {
+    /// Parses a manifest written in TOML.
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Parses a manifest written in JSON.
+    pub fn from_json_str(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Loads a manifest from a `.toml` or a `.json` file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&content),
+            Some("json") => Self::from_json_str(&content),
+            _ => bail!("Unsupported manifest format: {}", path.display()),
+        }
+    }
+
+    /// Returns whether the app declares a hazard, either directly or
+    /// through its category.
+    pub fn declares(&self, hazard: Hazard) -> bool {
+        self.justification(hazard).is_some()
+    }
+
+    /// Returns the justification given for a hazard.
+    ///
+    /// A justification given for the hazard itself takes precedence over
+    /// the one given for its category.
+    pub fn justification(&self, hazard: Hazard) -> Option<&str> {
+        self.hazards
+            .iter()
+            .find(|request| request.hazard == hazard)
+            .map(|request| request.justification.as_str())
+            .or_else(|| {
+                self.categories
+                    .iter()
+                    .find(|request| request.category == hazard.has_category())
+                    .map(|request| request.justification.as_str())
+            })
+    }
+
+    /// Returns all the hazards declared by the app, expanding the categories.
+    pub fn requested_hazards(&self) -> BTreeSet<Hazard> {
+        self.hazards
+            .iter()
+            .map(|request| request.hazard)
+            .chain(
+                self.categories
+                    .iter()
+                    .flat_map(|request| request.category.all_hazards().iter().copied()),
+            )
+            .collect()
+    }
+}
+
+/// Error returned when an interaction involves hazards the app did not
+/// declare or the user did not grant.
+#[derive(Clone, Debug)]
+pub struct NotGranted {
+    /// Name of the app.
+    pub app: String,
+    /// Identifier of the Thing.
+    pub thing: String,
+    /// Kind of interaction.
+    pub interaction: Interaction,
+    /// Name of the affordance.
+    pub affordance: String,
+    /// Hazards missing from the app manifest.
+    pub undeclared: Vec<String>,
+    /// Hazards declared in the manifest but not granted by the user.
+    pub ungranted: Vec<Hazard>,
+}
+
+impl fmt::Display for NotGranted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} `{}` of `{}` blocked for app `{}`",
+            self.interaction, self.affordance, self.thing, self.app
+        )?;
+        if !self.undeclared.is_empty() {
+            write!(f, ", undeclared hazards: {}", self.undeclared.join(", "))?;
+        }
+        if !self.ungranted.is_empty() {
+            let ungranted: Vec<_> = self.ungranted.iter().map(Hazard::to_string).collect();
+            write!(f, ", ungranted hazards: {}", ungranted.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NotGranted {}
+
+/// Hazards granted by the user to each app.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentStore {
+    granted: BTreeMap<String, BTreeSet<Hazard>>,
+}
+
+impl ConsentStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a store from a JSON file.
+    ///
+    /// It returns an empty store if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Saves the store to a JSON file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Grants a hazard to an app.
+    pub fn grant(&mut self, app: &str, hazard: Hazard) {
+        self.granted
+            .entry(app.to_owned())
+            .or_default()
+            .insert(hazard);
+    }
+
+    /// Grants all the hazards of a category to an app.
+    pub fn grant_category(&mut self, app: &str, category: Category) {
+        self.granted
+            .entry(app.to_owned())
+            .or_default()
+            .extend(category.all_hazards());
+    }
+
+    /// Grants all the hazards requested by a manifest.
+    pub fn grant_manifest(&mut self, manifest: &AppManifest) {
+        self.granted
+            .entry(manifest.name.clone())
+            .or_default()
+            .extend(manifest.requested_hazards());
+    }
+
+    /// Revokes a hazard from an app.
+    pub fn revoke(&mut self, app: &str, hazard: Hazard) {
+        if let Some(granted) = self.granted.get_mut(app) {
+            granted.remove(&hazard);
+        }
+    }
+
+    /// Revokes all the hazards granted to an app.
+    pub fn revoke_all(&mut self, app: &str) {
+        self.granted.remove(app);
+    }
+
+    /// Returns whether a hazard has been granted to an app.
+    pub fn is_granted(&self, app: &str, hazard: Hazard) -> bool {
+        self.granted
+            .get(app)
+            .is_some_and(|granted| granted.contains(&hazard))
+    }
+
+    /// Returns the hazards granted to an app.
+    pub fn granted(&self, app: &str) -> impl Iterator<Item = Hazard> + '_ {
+        self.granted.get(app).into_iter().flatten().copied()
+    }
+
+    /// Checks whether all the hazards of an interaction have been declared
+    /// by the app and granted by the user.
+    ///
+    /// Hazards not defined by the ontology cannot be declared, so they
+    /// always block the interaction.
+    pub fn check(&self, manifest: &AppManifest, request: &Request) -> Result<(), Box<NotGranted>> {
+        let mut undeclared = Vec::new();
+        let mut ungranted = Vec::new();
+
+        for thing_hazard in request.hazards {
+            match Hazard::has_hazard(thing_hazard) {
+                Some(hazard) if !manifest.declares(hazard) => undeclared.push(hazard.to_string()),
+                Some(hazard) if !self.is_granted(&manifest.name, hazard) => ungranted.push(hazard),
+                Some(_) => {}
+                None => undeclared.push(thing_hazard.name.clone()),
+            }
+        }
+
+        if undeclared.is_empty() && ungranted.is_empty() {
+            Ok(())
+        } else {
+            Err(Box::new(NotGranted {
+                app: manifest.name.clone(),
+                thing: request.thing.id.clone(),
+                interaction: request.interaction,
+                affordance: request.affordance.to_owned(),
+                undeclared,
+                ungranted,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::thing::Thing;
+    use crate::ConsumedThing;
+
+    const MANIFEST: &str = r#"
+        name = "oven-controller"
+
+        [[hazards]]
+        hazard = "sho:FireHazard"
+        justification = "Turns the oven on at the scheduled time"
+
+        [[categories]]
+        category = "Financial"
+        justification = "Monitors the energy consumption"
+    "#;
+
+    #[test]
+    fn manifest() {
+        let manifest = AppManifest::from_toml_str(MANIFEST).unwrap();
+
+        assert!(manifest.declares(Hazard::FireHazard));
+        assert!(manifest.declares(Hazard::GasConsumption));
+        assert!(!manifest.declares(Hazard::TakePictures));
+        assert_eq!(
+            manifest.justification(Hazard::SpendMoney),
+            Some("Monitors the energy consumption")
+        );
+        assert_eq!(manifest.requested_hazards().len(), 6);
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert_eq!(AppManifest::from_json_str(&json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn consent() {
+        let manifest = AppManifest::from_toml_str(MANIFEST).unwrap();
+        let thing: Thing = serde_json::from_str(
+            r#"{
+                "@context": "https://www.w3.org/2019/wot/td/v1",
+                "id": "urn:dev:ops:my-oven-1234",
+                "title": "MyOven",
+                "securityDefinitions": {},
+                "actions": {
+                    "bake": {
+                        "hazards": [
+                            {
+                                "@id": "sho:FireHazard",
+                                "name": "FireHazard",
+                                "description": "The execution may cause fire"
+                            },
+                            {
+                                "@id": "sho:AirPoisoning",
+                                "name": "AirPoisoning",
+                                "description": "The execution may release toxic gases"
+                            }
+                        ],
+                        "forms": [{"href": "http://192.0.2.1/bake"}]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut store = ConsentStore::new();
+        store.grant(&manifest.name, Hazard::AirPoisoning);
+
+        let consumed = ConsumedThing::new(thing).with_consent(manifest.clone(), store.clone());
+        let err = consumed.invoke_action::<()>("bake", None).unwrap_err();
+        let not_granted = err.downcast_ref::<NotGranted>().unwrap();
+        assert_eq!(not_granted.undeclared, ["AirPoisoning"]);
+        assert_eq!(not_granted.ungranted, [Hazard::FireHazard]);
+
+        store.grant_manifest(&manifest);
+        assert!(store.is_granted(&manifest.name, Hazard::FireHazard));
+        store.revoke(&manifest.name, Hazard::FireHazard);
+        assert!(!store.is_granted(&manifest.name, Hazard::FireHazard));
+        assert_eq!(store.granted(&manifest.name).count(), 6);
+    }
+}
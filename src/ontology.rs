@@ -1,8 +1,68 @@
-#![allow(clippy::enum_variant_names)]
-
+use std::fmt;
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+use std::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
 use crate::thing::Hazard as ThingHazard;
-
+/// IRI of the SIFIS hazards ontology.
+pub const ONTOLOGY_IRI: &str = "https://purl.org/sifis/hazards";
+/// Namespace of the SIFIS hazards ontology.
+pub const ONTOLOGY_NAMESPACE: &str = "https://purl.org/sifis/hazards#";
+/// Title of the SIFIS hazards ontology.
+pub const ONTOLOGY_TITLE: &str = "The SIFIS-Home Hazards Ontology";
+/// Version of the SIFIS hazards ontology this code has been generated from.
+pub const ONTOLOGY_VERSION: &str = "v1.0.1";
+/// Last modification date of the SIFIS hazards ontology.
+pub const ONTOLOGY_MODIFIED: &str = "2022-01-20";
+/// Lowest risk score admitted by the ontology.
+pub const RISK_SCORE_MIN: u8 = 0;
+/// Highest risk score admitted by the ontology.
+pub const RISK_SCORE_MAX: u8 = 10;
+/// Prefixes of the ontology terms: the compact IRI prefix declared by the
+/// ontology, if any, and the namespace.
+const PREFIXES: &[&str] = &["sho:", ONTOLOGY_NAMESPACE];
+/// Returns the local name of an ontology term, which can be written as a
+/// plain name, as a compact IRI or as a full IRI.
+fn local_name(s: &str) -> &str {
+    PREFIXES.iter().find_map(|prefix| s.strip_prefix(prefix)).unwrap_or(s)
+}
+/// Texts of an ontology annotation, as `(language, text)` pairs.
+type LocalizedTexts = &'static [(&'static str, &'static str)];
+/// Picks the text in the requested language, falling back to its primary
+/// language subtag, then to English, to untagged texts and finally to the
+/// first available text.
+fn localized(texts: LocalizedTexts, lang: &str) -> Option<&'static str> {
+    let primary = lang.split('-').next().unwrap_or(lang);
+    [lang, primary, "en", ""]
+        .iter()
+        .find_map(|l| texts.iter().find(|(t, _)| t.eq_ignore_ascii_case(l)))
+        .or_else(|| texts.first())
+        .map(|(_, text)| *text)
+}
+/// Error returned when a string does not identify any `Hazard`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseHazardError(pub String);
+impl fmt::Display for ParseHazardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown hazard `{}`", self.0)
+    }
+}
+impl std::error::Error for ParseHazardError {}
+/// Error returned when a string does not identify any `Category`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseCategoryError(pub String);
+impl fmt::Display for ParseCategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown category `{}`", self.0)
+    }
+}
+impl std::error::Error for ParseCategoryError {}
 /// Hazards type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(clippy::enum_variant_names)]
 pub enum Hazard {
     /// The execution may release toxic gases
     AirPoisoning,
@@ -20,7 +80,8 @@ pub enum Hazard {
     FireHazard,
     /// The execution enables a device that consumes gas
     GasConsumption,
-    /// The execution authorises the app to get and save information about the app's energy impact on the device the app runs on
+    /// The execution authorises the app to get and save information about the app's energy impact
+    /// on the device the app runs on
     LogEnergyConsumption,
     /// The execution authorises the app to get and save information about the app's duration of use
     LogUsageTime,
@@ -42,16 +103,102 @@ pub enum Hazard {
     TakeDeviceScreenshots,
     /// The execution authorises the app to use a camera and take photos
     TakePictures,
-    /// The execution disables a protection mechanism and unauthorised individuals may physically enter home
+    /// The execution disables a protection mechanism and unauthorised individuals may physically
+    /// enter home
     UnauthorisedPhysicalAccess,
     /// The execution enables a device that consumes water
     WaterConsumption,
     /// The execution allows water usage which may lead to flood
     WaterFlooding,
 }
-
-impl ToString for Hazard {
-    fn to_string(&self) -> String {
+impl fmt::Display for Hazard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+impl FromStr for Hazard {
+    type Err = ParseHazardError;
+    /// Parses a `Hazard` from its name, its compact IRI or its full IRI.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match local_name(s) {
+            "AirPoisoning" => Ok(Self::AirPoisoning),
+            "Asphyxia" => Ok(Self::Asphyxia),
+            "AudioVideoRecordAndStore" => Ok(Self::AudioVideoRecordAndStore),
+            "AudioVideoStream" => Ok(Self::AudioVideoStream),
+            "ElectricEnergyConsumption" => Ok(Self::ElectricEnergyConsumption),
+            "Explosion" => Ok(Self::Explosion),
+            "FireHazard" => Ok(Self::FireHazard),
+            "GasConsumption" => Ok(Self::GasConsumption),
+            "LogEnergyConsumption" => Ok(Self::LogEnergyConsumption),
+            "LogUsageTime" => Ok(Self::LogUsageTime),
+            "PaySubscriptionFee" => Ok(Self::PaySubscriptionFee),
+            "PowerOutage" => Ok(Self::PowerOutage),
+            "PowerSurge" => Ok(Self::PowerSurge),
+            "RecordIssuedCommands" => Ok(Self::RecordIssuedCommands),
+            "RecordUserPreferences" => Ok(Self::RecordUserPreferences),
+            "SpendMoney" => Ok(Self::SpendMoney),
+            "SpoiledFood" => Ok(Self::SpoiledFood),
+            "TakeDeviceScreenshots" => Ok(Self::TakeDeviceScreenshots),
+            "TakePictures" => Ok(Self::TakePictures),
+            "UnauthorisedPhysicalAccess" => Ok(Self::UnauthorisedPhysicalAccess),
+            "WaterConsumption" => Ok(Self::WaterConsumption),
+            "WaterFlooding" => Ok(Self::WaterFlooding),
+            _ => Err(ParseHazardError(s.to_owned())),
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl TryFrom<&ThingHazard> for Hazard {
+    type Error = ParseHazardError;
+    /// Resolves the `@id` of a Thing hazard, falling back to its name.
+    fn try_from(thing_hazard: &ThingHazard) -> Result<Self, Self::Error> {
+        thing_hazard.id.parse().or_else(|_| thing_hazard.name.parse())
+    }
+}
+#[cfg(feature = "serde")]
+impl Serialize for Hazard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Hazard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+impl Hazard {
+    /// All the `Hazard` types.
+    pub const ALL: [Self; 22] = [
+        Self::AirPoisoning,
+        Self::Asphyxia,
+        Self::AudioVideoRecordAndStore,
+        Self::AudioVideoStream,
+        Self::ElectricEnergyConsumption,
+        Self::Explosion,
+        Self::FireHazard,
+        Self::GasConsumption,
+        Self::LogEnergyConsumption,
+        Self::LogUsageTime,
+        Self::PaySubscriptionFee,
+        Self::PowerOutage,
+        Self::PowerSurge,
+        Self::RecordIssuedCommands,
+        Self::RecordUserPreferences,
+        Self::SpendMoney,
+        Self::SpoiledFood,
+        Self::TakeDeviceScreenshots,
+        Self::TakePictures,
+        Self::UnauthorisedPhysicalAccess,
+        Self::WaterConsumption,
+        Self::WaterFlooding,
+    ];
+    /// Returns an iterator over all the `Hazard` types.
+    pub fn iter() -> std::array::IntoIter<Self, 22> {
+        Self::ALL.into_iter()
+    }
+    /// Returns the name of the hazard.
+    pub const fn name(self) -> &'static str {
         match self {
             Self::AirPoisoning => "AirPoisoning",
             Self::Asphyxia => "Asphyxia",
@@ -76,71 +223,363 @@ impl ToString for Hazard {
             Self::WaterConsumption => "WaterConsumption",
             Self::WaterFlooding => "WaterFlooding",
         }
-        .to_owned()
     }
-}
-
-impl Hazard {
+    /// Returns the bit of the hazard in a `HazardSet`.
+    ///
+    /// Bits are recorded in the lockfile of the generator, so they do not
+    /// change when other hazards are added to or removed from the ontology.
+    pub const fn bit(self) -> u32 {
+        match self {
+            Self::AirPoisoning => 0,
+            Self::Asphyxia => 1,
+            Self::AudioVideoRecordAndStore => 2,
+            Self::AudioVideoStream => 3,
+            Self::ElectricEnergyConsumption => 4,
+            Self::Explosion => 5,
+            Self::FireHazard => 6,
+            Self::GasConsumption => 7,
+            Self::LogEnergyConsumption => 8,
+            Self::LogUsageTime => 9,
+            Self::PaySubscriptionFee => 10,
+            Self::PowerOutage => 11,
+            Self::PowerSurge => 12,
+            Self::RecordIssuedCommands => 13,
+            Self::RecordUserPreferences => 14,
+            Self::SpendMoney => 15,
+            Self::SpoiledFood => 16,
+            Self::TakeDeviceScreenshots => 17,
+            Self::TakePictures => 18,
+            Self::UnauthorisedPhysicalAccess => 19,
+            Self::WaterConsumption => 20,
+            Self::WaterFlooding => 21,
+        }
+    }
     /// Returns the `Hazard` type associated to a Thing.
     ///
     /// It returns `None` if any type has been found for the given
     /// Thing.
+    #[cfg(feature = "serde")]
     pub fn has_hazard(thing_hazard: &ThingHazard) -> Option<Self> {
-        match thing_hazard.name.as_str() {
-            "AirPoisoning" => Some(Self::AirPoisoning),
-            "Asphyxia" => Some(Self::Asphyxia),
-            "AudioVideoRecordAndStore" => Some(Self::AudioVideoRecordAndStore),
-            "AudioVideoStream" => Some(Self::AudioVideoStream),
-            "ElectricEnergyConsumption" => Some(Self::ElectricEnergyConsumption),
-            "Explosion" => Some(Self::Explosion),
-            "FireHazard" => Some(Self::FireHazard),
-            "GasConsumption" => Some(Self::GasConsumption),
-            "LogEnergyConsumption" => Some(Self::LogEnergyConsumption),
-            "LogUsageTime" => Some(Self::LogUsageTime),
-            "PaySubscriptionFee" => Some(Self::PaySubscriptionFee),
-            "PowerOutage" => Some(Self::PowerOutage),
-            "PowerSurge" => Some(Self::PowerSurge),
-            "RecordIssuedCommands" => Some(Self::RecordIssuedCommands),
-            "RecordUserPreferences" => Some(Self::RecordUserPreferences),
-            "SpendMoney" => Some(Self::SpendMoney),
-            "SpoiledFood" => Some(Self::SpoiledFood),
-            "TakeDeviceScreenshots" => Some(Self::TakeDeviceScreenshots),
-            "TakePictures" => Some(Self::TakePictures),
-            "UnauthorisedPhysicalAccess" => Some(Self::UnauthorisedPhysicalAccess),
-            "WaterConsumption" => Some(Self::WaterConsumption),
-            "WaterFlooding" => Some(Self::WaterFlooding),
-            _ => None,
-        }
+        Self::try_from(thing_hazard).ok()
     }
-
     /// Returns the description associated to an `Hazard` type.
     pub fn get_description(&self) -> &str {
         match self {
-                Self::AirPoisoning => "The execution may release toxic gases",
-                Self::Asphyxia => "The execution may cause oxygen deficiency by gaseous substances",
-                Self::AudioVideoRecordAndStore => "The execution authorises the app to record and save a video with audio on persistent storage",
-                Self::AudioVideoStream => "The execution authorises the app to obtain a video stream with audio",
-                Self::ElectricEnergyConsumption => "The execution enables a device that consumes electricity",
-                Self::Explosion => "The execution may cause an explosion",
-                Self::FireHazard => "The execution may cause fire",
-                Self::GasConsumption => "The execution enables a device that consumes gas",
-                Self::LogEnergyConsumption => "The execution authorises the app to get and save information about the app's energy impact on the device the app runs on",
-                Self::LogUsageTime => "The execution authorises the app to get and save information about the app's duration of use",
-                Self::PaySubscriptionFee => "The execution authorises the app to use payment information and make a periodic payment",
-                Self::PowerOutage => "The execution may cause an interruption in the supply of electricity",
-                Self::PowerSurge => "The execution may lead to exposure to high voltages",
-                Self::RecordIssuedCommands => "The execution authorises the app to get and save user inputs",
-                Self::RecordUserPreferences => "The execution authorises the app to get and save information about the user's preferences",
-                Self::SpendMoney => "The execution authorises the app to use payment information and make a payment transaction",
-                Self::SpoiledFood => "The execution may lead to rotten food",
-                Self::TakeDeviceScreenshots => "The execution authorises the app to read the display output and take screenshots of it",
-                Self::TakePictures => "The execution authorises the app to use a camera and take photos",
-                Self::UnauthorisedPhysicalAccess => "The execution disables a protection mechanism and unauthorised individuals may physically enter home",
-                Self::WaterConsumption => "The execution enables a device that consumes water",
-                Self::WaterFlooding => "The execution allows water usage which may lead to flood",
+            Self::AirPoisoning => "The execution may release toxic gases",
+            Self::Asphyxia => {
+                "The execution may cause oxygen deficiency by gaseous substances"
+            }
+            Self::AudioVideoRecordAndStore => {
+                "The execution authorises the app to record and save a video with audio on persistent storage"
+            }
+            Self::AudioVideoStream => {
+                "The execution authorises the app to obtain a video stream with audio"
+            }
+            Self::ElectricEnergyConsumption => {
+                "The execution enables a device that consumes electricity"
+            }
+            Self::Explosion => "The execution may cause an explosion",
+            Self::FireHazard => "The execution may cause fire",
+            Self::GasConsumption => "The execution enables a device that consumes gas",
+            Self::LogEnergyConsumption => {
+                "The execution authorises the app to get and save information about the app's energy impact on the device the app runs on"
+            }
+            Self::LogUsageTime => {
+                "The execution authorises the app to get and save information about the app's duration of use"
+            }
+            Self::PaySubscriptionFee => {
+                "The execution authorises the app to use payment information and make a periodic payment"
+            }
+            Self::PowerOutage => {
+                "The execution may cause an interruption in the supply of electricity"
+            }
+            Self::PowerSurge => "The execution may lead to exposure to high voltages",
+            Self::RecordIssuedCommands => {
+                "The execution authorises the app to get and save user inputs"
+            }
+            Self::RecordUserPreferences => {
+                "The execution authorises the app to get and save information about the user's preferences"
+            }
+            Self::SpendMoney => {
+                "The execution authorises the app to use payment information and make a payment transaction"
+            }
+            Self::SpoiledFood => "The execution may lead to rotten food",
+            Self::TakeDeviceScreenshots => {
+                "The execution authorises the app to read the display output and take screenshots of it"
+            }
+            Self::TakePictures => {
+                "The execution authorises the app to use a camera and take photos"
+            }
+            Self::UnauthorisedPhysicalAccess => {
+                "The execution disables a protection mechanism and unauthorised individuals may physically enter home"
+            }
+            Self::WaterConsumption => {
+                "The execution enables a device that consumes water"
+            }
+            Self::WaterFlooding => {
+                "The execution allows water usage which may lead to flood"
+            }
+        }
+    }
+    fn descriptions(&self) -> LocalizedTexts {
+        match self {
+            Self::AirPoisoning => &[("", "The execution may release toxic gases")],
+            Self::Asphyxia => {
+                &[
+                    (
+                        "",
+                        "The execution may cause oxygen deficiency by gaseous substances",
+                    ),
+                ]
+            }
+            Self::AudioVideoRecordAndStore => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to record and save a video with audio on persistent storage",
+                    ),
+                ]
+            }
+            Self::AudioVideoStream => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to obtain a video stream with audio",
+                    ),
+                ]
+            }
+            Self::ElectricEnergyConsumption => {
+                &[("", "The execution enables a device that consumes electricity")]
+            }
+            Self::Explosion => &[("", "The execution may cause an explosion")],
+            Self::FireHazard => &[("", "The execution may cause fire")],
+            Self::GasConsumption => {
+                &[("", "The execution enables a device that consumes gas")]
+            }
+            Self::LogEnergyConsumption => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to get and save information about the app's energy impact on the device the app runs on",
+                    ),
+                ]
+            }
+            Self::LogUsageTime => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to get and save information about the app's duration of use",
+                    ),
+                ]
+            }
+            Self::PaySubscriptionFee => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to use payment information and make a periodic payment",
+                    ),
+                ]
+            }
+            Self::PowerOutage => {
+                &[
+                    (
+                        "",
+                        "The execution may cause an interruption in the supply of electricity",
+                    ),
+                ]
+            }
+            Self::PowerSurge => {
+                &[("", "The execution may lead to exposure to high voltages")]
+            }
+            Self::RecordIssuedCommands => {
+                &[("", "The execution authorises the app to get and save user inputs")]
+            }
+            Self::RecordUserPreferences => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to get and save information about the user's preferences",
+                    ),
+                ]
+            }
+            Self::SpendMoney => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to use payment information and make a payment transaction",
+                    ),
+                ]
+            }
+            Self::SpoiledFood => &[("", "The execution may lead to rotten food")],
+            Self::TakeDeviceScreenshots => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to read the display output and take screenshots of it",
+                    ),
+                ]
+            }
+            Self::TakePictures => {
+                &[
+                    (
+                        "",
+                        "The execution authorises the app to use a camera and take photos",
+                    ),
+                ]
+            }
+            Self::UnauthorisedPhysicalAccess => {
+                &[
+                    (
+                        "",
+                        "The execution disables a protection mechanism and unauthorised individuals may physically enter home",
+                    ),
+                ]
+            }
+            Self::WaterConsumption => {
+                &[("", "The execution enables a device that consumes water")]
+            }
+            Self::WaterFlooding => {
+                &[("", "The execution allows water usage which may lead to flood")]
+            }
+        }
+    }
+    fn labels(&self) -> LocalizedTexts {
+        match self {
+            Self::AirPoisoning => &[("en", "AirPoisoning")],
+            Self::Asphyxia => &[("en", "Asphyxia")],
+            Self::AudioVideoRecordAndStore => &[("en", "AudioVideoRecordAndStore")],
+            Self::AudioVideoStream => &[("en", "AudioVideoStream")],
+            Self::ElectricEnergyConsumption => &[("en", "ElectricEnergyConsumption")],
+            Self::Explosion => &[("en", "Explosion")],
+            Self::FireHazard => &[("en", "FireHazard")],
+            Self::GasConsumption => &[("en", "GasConsumption")],
+            Self::LogEnergyConsumption => &[("en", "LogEnergyConsumption")],
+            Self::LogUsageTime => &[("en", "LogUsageTime")],
+            Self::PaySubscriptionFee => &[("en", "PaySubscriptionFee")],
+            Self::PowerOutage => &[("en", "PowerOutage")],
+            Self::PowerSurge => &[("en", "PowerSurge")],
+            Self::RecordIssuedCommands => &[("en", "RecordIssuedCommands")],
+            Self::RecordUserPreferences => &[("en", "RecordUserPreferences")],
+            Self::SpendMoney => &[("en", "SpendMoney")],
+            Self::SpoiledFood => &[("en", "SpoiledFood")],
+            Self::TakeDeviceScreenshots => &[("en", "TakeDeviceScreenshots")],
+            Self::TakePictures => &[("en", "TakePictures")],
+            Self::UnauthorisedPhysicalAccess => &[("en", "UnauthorisedPhysicalAccess")],
+            Self::WaterConsumption => &[("en", "WaterConsumption")],
+            Self::WaterFlooding => &[("en", "WaterFlooding")],
+        }
+    }
+    fn comments(&self) -> LocalizedTexts {
+        match self {
+            Self::AirPoisoning => &[("en", "A type of safety-related hazard")],
+            Self::Asphyxia => &[("en", "A type of safety-related hazard")],
+            Self::AudioVideoRecordAndStore => {
+                &[("en", "A type of privacy-related hazard")]
+            }
+            Self::AudioVideoStream => &[("en", "A type of privacy-related hazard")],
+            Self::ElectricEnergyConsumption => {
+                &[("en", "A type of finance-related hazard")]
+            }
+            Self::Explosion => &[("en", "A type of safety-related hazard")],
+            Self::FireHazard => &[("en", "A type of safety-related hazard")],
+            Self::GasConsumption => &[("en", "A type of finance-related hazard")],
+            Self::LogEnergyConsumption => &[("en", "A type of privacy-related hazard")],
+            Self::LogUsageTime => &[("en", "A type of privacy-related hazard")],
+            Self::PaySubscriptionFee => &[("en", "A type of finance-related hazard")],
+            Self::PowerOutage => &[("en", "A type of safety-related hazard")],
+            Self::PowerSurge => &[("en", "A type of safety-related hazard")],
+            Self::RecordIssuedCommands => &[("en", "A type of privacy-related hazard")],
+            Self::RecordUserPreferences => &[("en", "A type of privacy-related hazard")],
+            Self::SpendMoney => &[("en", "A type of finance-related hazard")],
+            Self::SpoiledFood => &[("en", "A type of safety-related hazard")],
+            Self::TakeDeviceScreenshots => &[("en", "A type of privacy-related hazard")],
+            Self::TakePictures => &[("en", "A type of privacy-related hazard")],
+            Self::UnauthorisedPhysicalAccess => {
+                &[("en", "A type of safety-related hazard")]
+            }
+            Self::WaterConsumption => &[("en", "A type of finance-related hazard")],
+            Self::WaterFlooding => &[("en", "A type of safety-related hazard")],
         }
     }
-
+    /// Returns the description in the given language.
+    ///
+    /// It falls back to the primary language subtag, then to English
+    /// and finally to any available description.
+    pub fn get_description_for(&self, lang: &str) -> &str {
+        localized(self.descriptions(), lang).unwrap_or_else(|| self.get_description())
+    }
+    /// Returns the label in English.
+    pub fn label(&self) -> &str {
+        self.label_for("en")
+    }
+    /// Returns the label in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn label_for(&self, lang: &str) -> &str {
+        localized(self.labels(), lang).unwrap_or_default()
+    }
+    /// Returns the comment in English, if any.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment_for("en")
+    }
+    /// Returns the comment in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn comment_for(&self, lang: &str) -> Option<&str> {
+        localized(self.comments(), lang)
+    }
+    /// Returns the IRI of the `Hazard`.
+    pub fn iri(&self) -> &'static str {
+        match self {
+            Self::AirPoisoning => "https://purl.org/sifis/hazards#AirPoisoning",
+            Self::Asphyxia => "https://purl.org/sifis/hazards#Asphyxia",
+            Self::AudioVideoRecordAndStore => {
+                "https://purl.org/sifis/hazards#AudioVideoRecordAndStore"
+            }
+            Self::AudioVideoStream => "https://purl.org/sifis/hazards#AudioVideoStream",
+            Self::ElectricEnergyConsumption => {
+                "https://purl.org/sifis/hazards#ElectricEnergyConsumption"
+            }
+            Self::Explosion => "https://purl.org/sifis/hazards#Explosion",
+            Self::FireHazard => "https://purl.org/sifis/hazards#FireHazard",
+            Self::GasConsumption => "https://purl.org/sifis/hazards#GasConsumption",
+            Self::LogEnergyConsumption => {
+                "https://purl.org/sifis/hazards#LogEnergyConsumption"
+            }
+            Self::LogUsageTime => "https://purl.org/sifis/hazards#LogUsageTime",
+            Self::PaySubscriptionFee => {
+                "https://purl.org/sifis/hazards#PaySubscriptionFee"
+            }
+            Self::PowerOutage => "https://purl.org/sifis/hazards#PowerOutage",
+            Self::PowerSurge => "https://purl.org/sifis/hazards#PowerSurge",
+            Self::RecordIssuedCommands => {
+                "https://purl.org/sifis/hazards#RecordIssuedCommands"
+            }
+            Self::RecordUserPreferences => {
+                "https://purl.org/sifis/hazards#RecordUserPreferences"
+            }
+            Self::SpendMoney => "https://purl.org/sifis/hazards#SpendMoney",
+            Self::SpoiledFood => "https://purl.org/sifis/hazards#SpoiledFood",
+            Self::TakeDeviceScreenshots => {
+                "https://purl.org/sifis/hazards#TakeDeviceScreenshots"
+            }
+            Self::TakePictures => "https://purl.org/sifis/hazards#TakePictures",
+            Self::UnauthorisedPhysicalAccess => {
+                "https://purl.org/sifis/hazards#UnauthorisedPhysicalAccess"
+            }
+            Self::WaterConsumption => "https://purl.org/sifis/hazards#WaterConsumption",
+            Self::WaterFlooding => "https://purl.org/sifis/hazards#WaterFlooding",
+        }
+    }
+    /// Returns whether the ontology annotates the `Hazard` with a risk score.
+    pub fn has_risk_score(&self) -> bool {
+        matches!(
+            self, Self::ElectricEnergyConsumption | Self::FireHazard |
+            Self::GasConsumption | Self::PowerOutage | Self::WaterConsumption
+        )
+    }
+    /// Returns the default risk score defined by the ontology, if any.
+    pub fn default_risk_score(&self) -> Option<u8> {
+        None
+    }
     /// Returns the `Category`s associated to an `Hazard`.
     pub fn has_category(&self) -> Category {
         match self {
@@ -168,7 +607,6 @@ impl Hazard {
             Self::WaterFlooding => Category::Safety,
         }
     }
-
     /// Returns all `Hazard` types as immutable strings.
     pub fn all_hazards() -> &'static [&'static str] {
         &[
@@ -197,8 +635,8 @@ impl Hazard {
         ]
     }
 }
-
 /// Categories associated to an hazard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Category {
     /// Category which includes all the financial-related hazards.
     Financial,
@@ -207,55 +645,467 @@ pub enum Category {
     /// Category which includes all the safety-related hazards.
     Safety,
 }
-
-impl ToString for Category {
-    fn to_string(&self) -> String {
-        match self {
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
             Self::Financial => "Financial",
             Self::Privacy => "Privacy",
             Self::Safety => "Safety",
+        };
+        f.write_str(s)
+    }
+}
+impl FromStr for Category {
+    type Err = ParseCategoryError;
+    /// Parses a `Category` from its name, its compact IRI or its full IRI.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match local_name(s) {
+            "Financial" => Ok(Self::Financial),
+            "Privacy" => Ok(Self::Privacy),
+            "Safety" => Ok(Self::Safety),
+            _ => Err(ParseCategoryError(s.to_owned())),
         }
-        .to_owned()
     }
 }
-
+#[cfg(feature = "serde")]
+impl Serialize for Category {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Category {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
 impl Category {
+    /// All the `Category` types.
+    pub const ALL: [Self; 3] = [Self::Financial, Self::Privacy, Self::Safety];
+    /// Returns an iterator over all the `Category` types.
+    pub fn iter() -> std::array::IntoIter<Self, 3> {
+        Self::ALL.into_iter()
+    }
+    /// Returns the description associated to a `Category` type.
+    pub fn get_description(&self) -> &str {
+        match self {
+            Self::Financial => {
+                "Category which includes all the financial-related hazards."
+            }
+            Self::Privacy => "Category which includes all the privacy-related hazards.",
+            Self::Safety => "Category which includes all the safety-related hazards.",
+        }
+    }
+    fn descriptions(&self) -> LocalizedTexts {
+        match self {
+            Self::Financial => {
+                &[("", "Category which includes all the financial-related hazards.")]
+            }
+            Self::Privacy => {
+                &[("", "Category which includes all the privacy-related hazards.")]
+            }
+            Self::Safety => {
+                &[("", "Category which includes all the safety-related hazards.")]
+            }
+        }
+    }
+    fn labels(&self) -> LocalizedTexts {
+        match self {
+            Self::Financial => &[("en", "Financial")],
+            Self::Privacy => &[("en", "Privacy")],
+            Self::Safety => &[("en", "Safety")],
+        }
+    }
+    fn comments(&self) -> LocalizedTexts {
+        match self {
+            Self::Financial => {
+                &[
+                    (
+                        "en",
+                        "A type of category referring to hazards that lead to an expense",
+                    ),
+                ]
+            }
+            Self::Privacy => {
+                &[
+                    (
+                        "en",
+                        "A type of category referring to hazards that may compromise privacy",
+                    ),
+                ]
+            }
+            Self::Safety => {
+                &[
+                    (
+                        "en",
+                        "A type of category referring to hazards that may lead to physical harm to people and/or assets",
+                    ),
+                ]
+            }
+        }
+    }
+    /// Returns the description in the given language.
+    ///
+    /// It falls back to the primary language subtag, then to English
+    /// and finally to any available description.
+    pub fn get_description_for(&self, lang: &str) -> &str {
+        localized(self.descriptions(), lang).unwrap_or_else(|| self.get_description())
+    }
+    /// Returns the label in English.
+    pub fn label(&self) -> &str {
+        self.label_for("en")
+    }
+    /// Returns the label in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn label_for(&self, lang: &str) -> &str {
+        localized(self.labels(), lang).unwrap_or_default()
+    }
+    /// Returns the comment in English, if any.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment_for("en")
+    }
+    /// Returns the comment in the given language, with the same fallback
+    /// as `get_description_for`.
+    pub fn comment_for(&self, lang: &str) -> Option<&str> {
+        localized(self.comments(), lang)
+    }
+    /// Returns the IRI of the `Category`.
+    pub fn iri(&self) -> &'static str {
+        match self {
+            Self::Financial => "https://purl.org/sifis/hazards#Financial",
+            Self::Privacy => "https://purl.org/sifis/hazards#Privacy",
+            Self::Safety => "https://purl.org/sifis/hazards#Safety",
+        }
+    }
+    /// Returns the `HazardSet` of the `Hazard`s associated to a `Category`.
+    pub fn hazard_set(&self) -> HazardSet {
+        self.all_hazards().iter().copied().collect()
+    }
     /// Returns all `Hazard`s associated to a `Category`.
     pub fn all_hazards(&self) -> &[Hazard] {
         match self {
-            Self::Financial => &[
-                Hazard::ElectricEnergyConsumption,
-                Hazard::GasConsumption,
-                Hazard::PaySubscriptionFee,
-                Hazard::SpendMoney,
-                Hazard::WaterConsumption,
-            ],
-            Self::Privacy => &[
-                Hazard::AudioVideoRecordAndStore,
-                Hazard::AudioVideoStream,
-                Hazard::LogEnergyConsumption,
-                Hazard::LogUsageTime,
-                Hazard::RecordIssuedCommands,
-                Hazard::RecordUserPreferences,
-                Hazard::TakeDeviceScreenshots,
-                Hazard::TakePictures,
-            ],
-            Self::Safety => &[
-                Hazard::AirPoisoning,
-                Hazard::Asphyxia,
-                Hazard::Explosion,
-                Hazard::FireHazard,
-                Hazard::PowerOutage,
-                Hazard::PowerSurge,
-                Hazard::SpoiledFood,
-                Hazard::UnauthorisedPhysicalAccess,
-                Hazard::WaterFlooding,
-            ],
+            Self::Financial => {
+                &[
+                    Hazard::ElectricEnergyConsumption,
+                    Hazard::GasConsumption,
+                    Hazard::PaySubscriptionFee,
+                    Hazard::SpendMoney,
+                    Hazard::WaterConsumption,
+                ]
+            }
+            Self::Privacy => {
+                &[
+                    Hazard::AudioVideoRecordAndStore,
+                    Hazard::AudioVideoStream,
+                    Hazard::LogEnergyConsumption,
+                    Hazard::LogUsageTime,
+                    Hazard::RecordIssuedCommands,
+                    Hazard::RecordUserPreferences,
+                    Hazard::TakeDeviceScreenshots,
+                    Hazard::TakePictures,
+                ]
+            }
+            Self::Safety => {
+                &[
+                    Hazard::AirPoisoning,
+                    Hazard::Asphyxia,
+                    Hazard::Explosion,
+                    Hazard::FireHazard,
+                    Hazard::PowerOutage,
+                    Hazard::PowerSurge,
+                    Hazard::SpoiledFood,
+                    Hazard::UnauthorisedPhysicalAccess,
+                    Hazard::WaterFlooding,
+                ]
+            }
         }
     }
-
     /// Returns all `Category` as immutable strings.
     pub fn all_categories() -> &'static [&'static str] {
         &["Financial", "Privacy", "Safety"]
     }
 }
+/// Set of `Hazard`s, stored as the bits of a `u64`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HazardSet(u64);
+impl HazardSet {
+    /// The empty set.
+    pub const EMPTY: Self = Self(0);
+    /// The set of all the `Hazard`s.
+    pub const ALL: Self = {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < Hazard::ALL.len() {
+            bits |= 1 << Hazard::ALL[i].bit();
+            i += 1;
+        }
+        Self(bits)
+    };
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+    /// Creates a set containing a single `Hazard`.
+    pub const fn from_hazard(hazard: Hazard) -> Self {
+        Self(1 << hazard.bit())
+    }
+    /// Creates a set from its bits.
+    ///
+    /// It returns `None` if any bit does not belong to a `Hazard`.
+    pub const fn from_bits(bits: u64) -> Option<Self> {
+        if bits & !Self::ALL.0 == 0 { Some(Self(bits)) } else { None }
+    }
+    /// Creates a set from its bits, ignoring the ones which do not belong
+    /// to a `Hazard`.
+    pub const fn from_bits_truncate(bits: u64) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+    /// Returns the bits of the set, to be stored as a `u64`.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+    /// Creates a set from the names of its `Hazard`s.
+    pub fn from_names<'a>(
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, ParseHazardError> {
+        names.into_iter().map(str::parse::<Hazard>).collect()
+    }
+    /// Returns the names of the `Hazard`s of the set.
+    pub fn names(self) -> Vec<&'static str> {
+        self.iter().map(|hazard| hazard.name()).collect()
+    }
+    /// Returns whether the set contains no `Hazard`.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+    /// Returns the number of `Hazard`s of the set.
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+    /// Returns whether the set contains a `Hazard`.
+    pub const fn contains(self, hazard: Hazard) -> bool {
+        self.0 & Self::from_hazard(hazard).0 != 0
+    }
+    /// Adds a `Hazard` to the set, returning whether it was not present.
+    pub fn insert(&mut self, hazard: Hazard) -> bool {
+        let inserted = !self.contains(hazard);
+        self.0 |= Self::from_hazard(hazard).0;
+        inserted
+    }
+    /// Removes a `Hazard` from the set, returning whether it was present.
+    pub fn remove(&mut self, hazard: Hazard) -> bool {
+        let removed = self.contains(hazard);
+        self.0 &= !Self::from_hazard(hazard).0;
+        removed
+    }
+    /// Returns the `Hazard`s in either set.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+    /// Returns the `Hazard`s in both sets.
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+    /// Returns the `Hazard`s in this set but not in the other one.
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+    /// Returns the `Hazard`s in only one of the sets.
+    pub const fn symmetric_difference(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+    /// Returns the `Hazard`s not in the set.
+    pub const fn complement(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+    /// Returns whether all the `Hazard`s of the set are in the other one.
+    pub const fn is_subset(self, other: Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+    /// Returns whether all the `Hazard`s of the other set are in this one.
+    pub const fn is_superset(self, other: Self) -> bool {
+        other.is_subset(self)
+    }
+    /// Returns whether the sets have no `Hazard` in common.
+    pub const fn is_disjoint(self, other: Self) -> bool {
+        self.0 & other.0 == 0
+    }
+    /// Returns an iterator over the `Hazard`s of the set.
+    pub fn iter(self) -> impl Iterator<Item = Hazard> {
+        Hazard::iter().filter(move |hazard| self.contains(*hazard))
+    }
+}
+impl fmt::Debug for HazardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+impl From<Hazard> for HazardSet {
+    fn from(hazard: Hazard) -> Self {
+        Self::from_hazard(hazard)
+    }
+}
+impl From<HazardSet> for u64 {
+    fn from(set: HazardSet) -> Self {
+        set.bits()
+    }
+}
+impl FromIterator<Hazard> for HazardSet {
+    fn from_iter<I: IntoIterator<Item = Hazard>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+impl Extend<Hazard> for HazardSet {
+    fn extend<I: IntoIterator<Item = Hazard>>(&mut self, iter: I) {
+        for hazard in iter {
+            self.insert(hazard);
+        }
+    }
+}
+impl BitOr for HazardSet {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+impl BitOrAssign for HazardSet {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.union(other);
+    }
+}
+impl BitAnd for HazardSet {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+impl BitAndAssign for HazardSet {
+    fn bitand_assign(&mut self, other: Self) {
+        *self = self.intersection(other);
+    }
+}
+impl BitXor for HazardSet {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        self.symmetric_difference(other)
+    }
+}
+impl BitXorAssign for HazardSet {
+    fn bitxor_assign(&mut self, other: Self) {
+        *self = self.symmetric_difference(other);
+    }
+}
+impl Sub for HazardSet {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+impl SubAssign for HazardSet {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.difference(other);
+    }
+}
+impl Not for HazardSet {
+    type Output = Self;
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+/// Serialized as the list of the names of its `Hazard`s.
+#[cfg(feature = "serde")]
+impl Serialize for HazardSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for HazardSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Hazard>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn hazard() {
+        for hazard in Hazard::iter() {
+            assert_eq!(hazard.to_string(), hazard.name());
+            assert_eq!(hazard.name().parse(), Ok(hazard));
+            for prefix in PREFIXES {
+                assert_eq!(format!("{}{}", prefix, hazard) .parse(), Ok(hazard));
+            }
+            assert_eq!(hazard.iri().parse(), Ok(hazard));
+            assert_eq!(hazard.label_for("zz-ZZ"), hazard.label());
+            assert_eq!(hazard.comment_for("zz-ZZ"), hazard.comment());
+            assert_eq!(
+                hazard.get_description_for("zz-ZZ"), hazard.get_description_for("en")
+            );
+            assert!(hazard.has_category().hazard_set().contains(hazard));
+        }
+        assert_eq!("".parse::< Hazard > (), Err(ParseHazardError(String::new())));
+        for category in Category::iter() {
+            assert_eq!(category.to_string().parse(), Ok(category));
+            assert_eq!(category.iri().parse(), Ok(category));
+            assert_eq!(category.label_for("zz-ZZ"), category.label());
+        }
+        assert!("".parse::< Category > ().is_err());
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn thing_hazard() {
+        let hazard = Hazard::ALL[0];
+        let thing_hazard = |id: &str, name: &str| -> ThingHazard {
+            serde_json::from_value(
+                    serde_json::json!(
+                        { "@id" : id, "name" : name, "description" : hazard
+                        .get_description(), }
+                    ),
+                )
+                .unwrap()
+        };
+        assert_eq!(Hazard::try_from(& thing_hazard(hazard.iri(), "")), Ok(hazard));
+        assert_eq!(Hazard::has_hazard(& thing_hazard("", hazard.name())), Some(hazard));
+        assert_eq!(Hazard::has_hazard(& thing_hazard("", "")), None);
+        let json = serde_json::to_string(&hazard).unwrap();
+        assert_eq!(json, format!("\"{}\"", hazard));
+        assert_eq!(serde_json::from_str::< Hazard > (& json).unwrap(), hazard);
+        let compact = serde_json::json!(format!("{}{}", PREFIXES[0], hazard));
+        assert_eq!(serde_json::from_value::< Hazard > (compact).unwrap(), hazard);
+    }
+    #[test]
+    fn hazard_set() {
+        let first = Hazard::ALL[0];
+        let last = Hazard::ALL[Hazard::ALL.len() - 1];
+        let mut set = HazardSet::from(first);
+        assert!(set.insert(last));
+        assert!(! set.insert(last));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::< Vec < _ >> (), [first, last]);
+        assert_eq!(HazardSet::from_bits(set.bits()), Some(set));
+        assert_eq!(HazardSet::from_names(set.names()), Ok(set));
+        assert_eq!(
+            HazardSet::from_names(["Unknown"]), Err(ParseHazardError("Unknown"
+            .to_owned()))
+        );
+        assert_eq!(set | ! set, HazardSet::ALL);
+        assert_eq!(set & ! set, HazardSet::EMPTY);
+        assert_eq!(set - HazardSet::from(first), HazardSet::from(last));
+        assert!(HazardSet::from(first).is_subset(set));
+        assert!(set.is_disjoint(! set));
+        assert_eq!(HazardSet::ALL.len(), Hazard::ALL.len());
+        assert_eq!(HazardSet::from_bits_truncate(u64::MAX), HazardSet::ALL);
+        let categories = Category::iter()
+            .fold(
+                HazardSet::EMPTY,
+                |set, category| {
+                    assert!(set.is_disjoint(category.hazard_set()));
+                    set | category.hazard_set()
+                },
+            );
+        assert_eq!(categories, HazardSet::ALL);
+    }
+}
@@ -0,0 +1,394 @@
+//! Hazard-aware permission policy.
+//!
+//! A [`Policy`] is a list of user-defined [`Rule`]s deciding whether an
+//! interaction with a Thing can take place, given the hazards of the
+//! involved affordance.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ontology::{Category, Hazard};
+use crate::thing::{Hazard as ThingHazard, RiskScore, Thing};
+
+/// Outcome of a policy evaluation.
+///
+/// Decisions are ordered from the least to the most restrictive one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Decision {
+    /// The interaction can take place.
+    Allow,
+    /// The user has to confirm the interaction.
+    Ask,
+    /// The interaction must not take place.
+    Deny,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::Allow => "allow",
+            Self::Ask => "ask",
+            Self::Deny => "deny",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Kind of interaction with a Thing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interaction {
+    /// Write the value of a property.
+    WriteProperty,
+    /// Invoke an action.
+    InvokeAction,
+    /// Subscribe to an event.
+    SubscribeEvent,
+}
+
+impl fmt::Display for Interaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::WriteProperty => "writeproperty",
+            Self::InvokeAction => "invokeaction",
+            Self::SubscribeEvent => "subscribeevent",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Hazards a rule applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Matcher {
+    /// Any hazard, including the ones not defined by the ontology.
+    Any,
+    /// A single hazard.
+    Hazard(Hazard),
+    /// All the hazards of a category.
+    Category(Category),
+}
+
+impl Matcher {
+    fn matches(&self, hazard: Option<Hazard>) -> bool {
+        match (self, hazard) {
+            (Self::Any, _) => true,
+            (Self::Hazard(h), Some(hazard)) => *h == hazard,
+            (Self::Category(c), Some(hazard)) => *c == hazard.has_category(),
+            _ => false,
+        }
+    }
+}
+
+/// A policy rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rule {
+    /// Hazards the rule applies to.
+    pub matcher: Matcher,
+    /// The rule applies only to hazards with a risk score greater
+    /// than or equal to this one.
+    ///
+    /// A hazard without a risk score is considered as having the
+    /// highest one.
+    #[serde(default)]
+    pub min_risk_score: Option<RiskScore>,
+    /// Decision taken when the rule applies.
+    pub decision: Decision,
+}
+
+impl Rule {
+    /// Creates a new `Rule`.
+    pub fn new(matcher: Matcher, decision: Decision) -> Self {
+        Self {
+            matcher,
+            min_risk_score: None,
+            decision,
+        }
+    }
+
+    /// Restricts the rule to the hazards with at least the given risk score.
+    pub fn with_min_risk_score(mut self, score: RiskScore) -> Self {
+        self.min_risk_score = Some(score);
+        self
+    }
+
+    fn applies(&self, hazard: &ThingHazard) -> bool {
+        let score = hazard.risk_score().map_or(RiskScore::MAX, RiskScore::value);
+
+        self.matcher.matches(Hazard::has_hazard(hazard))
+            && self.min_risk_score.is_none_or(|min| score >= min.value())
+    }
+}
+
+/// Interaction checked against a policy.
+#[derive(Clone, Copy, Debug)]
+pub struct Request<'a> {
+    /// Thing exposing the affordance.
+    pub thing: &'a Thing,
+    /// Kind of interaction.
+    pub interaction: Interaction,
+    /// Name of the affordance.
+    pub affordance: &'a str,
+    /// Hazards of the affordance.
+    pub hazards: &'a [ThingHazard],
+}
+
+/// Error returned when a policy does not allow an interaction.
+#[derive(Clone, Debug)]
+pub struct Denied {
+    /// Identifier of the Thing.
+    pub thing: String,
+    /// Kind of interaction.
+    pub interaction: Interaction,
+    /// Name of the affordance.
+    pub affordance: String,
+    /// Decision taken by the policy, it is `Ask` when the user did
+    /// not confirm the interaction.
+    pub decision: Decision,
+    /// Hazards which led to the decision.
+    pub hazards: Vec<String>,
+}
+
+impl fmt::Display for Denied {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} `{}` of `{}` denied by policy ({})",
+            self.interaction, self.affordance, self.thing, self.decision
+        )?;
+        if !self.hazards.is_empty() {
+            write!(f, ", hazards: {}", self.hazards.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Denied {}
+
+type Prompt = Box<dyn Fn(&Request) -> bool + Send + Sync>;
+
+/// Hazard-aware permission policy.
+///
+/// Each hazard of an affordance is evaluated against the rules and the
+/// most restrictive decision among the matching rules is taken.
+/// When no rule matches, the default decision is taken.
+/// The decision for the whole interaction is the most restrictive one
+/// among its hazards, an interaction without hazards is always allowed.
+pub struct Policy {
+    rules: Vec<Rule>,
+    default: Decision,
+    prompt: Option<Prompt>,
+}
+
+impl Default for Policy {
+    /// Creates a policy allowing every interaction.
+    fn default() -> Self {
+        Self::new(Decision::Allow)
+    }
+}
+
+impl fmt::Debug for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Policy")
+            .field("rules", &self.rules)
+            .field("default", &self.default)
+            .field("prompt", &self.prompt.is_some())
+            .finish()
+    }
+}
+
+impl Policy {
+    /// Creates a policy without rules, taking the given default decision.
+    pub fn new(default: Decision) -> Self {
+        Self {
+            rules: Vec::new(),
+            default,
+            prompt: None,
+        }
+    }
+
+    /// Adds a rule to the policy.
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Allows the interactions involving the matched hazards.
+    pub fn allow(self, matcher: Matcher) -> Self {
+        self.rule(Rule::new(matcher, Decision::Allow))
+    }
+
+    /// Asks the user before the interactions involving the matched hazards.
+    pub fn ask(self, matcher: Matcher) -> Self {
+        self.rule(Rule::new(matcher, Decision::Ask))
+    }
+
+    /// Denies the interactions involving the matched hazards.
+    pub fn deny(self, matcher: Matcher) -> Self {
+        self.rule(Rule::new(matcher, Decision::Deny))
+    }
+
+    /// Sets the function asking the user to confirm an interaction.
+    ///
+    /// Without a prompt, interactions requiring a confirmation are denied.
+    pub fn with_prompt<F>(mut self, prompt: F) -> Self
+    where
+        F: Fn(&Request) -> bool + Send + Sync + 'static,
+    {
+        self.prompt = Some(Box::new(prompt));
+        self
+    }
+
+    /// Returns the rules of the policy.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns the decision taken for a single hazard.
+    pub fn evaluate_hazard(&self, hazard: &ThingHazard) -> Decision {
+        self.rules
+            .iter()
+            .filter(|rule| rule.applies(hazard))
+            .map(|rule| rule.decision)
+            .max()
+            .unwrap_or(self.default)
+    }
+
+    /// Returns the decision taken for an interaction, without asking
+    /// the user.
+    pub fn evaluate(&self, request: &Request) -> Decision {
+        request
+            .hazards
+            .iter()
+            .map(|hazard| self.evaluate_hazard(hazard))
+            .max()
+            .unwrap_or(Decision::Allow)
+    }
+
+    /// Checks whether an interaction can take place, asking the user
+    /// if needed.
+    ///
+    /// Returns the decision taken by the rules.
+    pub fn check(&self, request: &Request) -> Result<Decision, Denied> {
+        let decision = self.evaluate(request);
+        let allowed = match decision {
+            Decision::Allow => true,
+            Decision::Ask => self.prompt.as_ref().is_some_and(|ask| ask(request)),
+            Decision::Deny => false,
+        };
+
+        if allowed {
+            Ok(decision)
+        } else {
+            let hazards = request
+                .hazards
+                .iter()
+                .filter(|hazard| self.evaluate_hazard(hazard) == decision)
+                .map(|hazard| {
+                    Hazard::has_hazard(hazard)
+                        .map_or_else(|| hazard.name.clone(), |v| v.to_string())
+                })
+                .collect();
+
+            Err(Denied {
+                thing: request.thing.id.clone(),
+                interaction: request.interaction,
+                affordance: request.affordance.to_owned(),
+                decision,
+                hazards,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hazard(name: &str, risk_score: Option<u8>) -> ThingHazard {
+        serde_json::from_value(serde_json::json!({
+            "@id": format!("sho:{}", name),
+            "name": name,
+            "description": "",
+            "riskScore": risk_score,
+        }))
+        .unwrap()
+    }
+
+    fn thing() -> Thing {
+        serde_json::from_str(
+            r#"{
+                "@context": "https://www.w3.org/2019/wot/td/v1",
+                "id": "urn:dev:ops:my-oven-1234",
+                "title": "MyOven",
+                "securityDefinitions": {}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn decisions() {
+        let policy = Policy::new(Decision::Ask)
+            .allow(Matcher::Category(Category::Financial))
+            .deny(Matcher::Hazard(Hazard::FireHazard))
+            .rule(
+                Rule::new(Matcher::Category(Category::Safety), Decision::Deny)
+                    .with_min_risk_score(RiskScore::new(8).unwrap()),
+            )
+            .allow(Matcher::Category(Category::Safety));
+
+        let energy = hazard("ElectricEnergyConsumption", Some(2));
+        let fire = hazard("FireHazard", Some(1));
+        let outage = hazard("PowerOutage", Some(3));
+        let surge = hazard("PowerSurge", Some(9));
+        let flooding = hazard("WaterFlooding", None);
+        let pictures = hazard("TakePictures", Some(1));
+
+        assert_eq!(policy.evaluate_hazard(&energy), Decision::Allow);
+        assert_eq!(policy.evaluate_hazard(&fire), Decision::Deny);
+        assert_eq!(policy.evaluate_hazard(&outage), Decision::Allow);
+        assert_eq!(policy.evaluate_hazard(&surge), Decision::Deny);
+        assert_eq!(policy.evaluate_hazard(&flooding), Decision::Deny);
+        assert_eq!(policy.evaluate_hazard(&pictures), Decision::Ask);
+
+        let thing = thing();
+        let hazards = [energy, outage];
+        let mut request = Request {
+            thing: &thing,
+            interaction: Interaction::InvokeAction,
+            affordance: "bake",
+            hazards: &hazards,
+        };
+        assert_eq!(policy.check(&request).unwrap(), Decision::Allow);
+
+        let hazards = [hazards[0].clone(), surge];
+        request.hazards = &hazards;
+        let denied = policy.check(&request).unwrap_err();
+        assert_eq!(denied.decision, Decision::Deny);
+        assert_eq!(denied.hazards, ["PowerSurge"]);
+
+        request.hazards = &[];
+        assert_eq!(policy.evaluate(&request), Decision::Allow);
+    }
+
+    #[test]
+    fn ask() {
+        let thing = thing();
+        let hazards = [hazard("TakePictures", None)];
+        let request = Request {
+            thing: &thing,
+            interaction: Interaction::SubscribeEvent,
+            affordance: "motion",
+            hazards: &hazards,
+        };
+
+        let policy = Policy::new(Decision::Ask);
+        assert_eq!(policy.check(&request).unwrap_err().decision, Decision::Ask);
+
+        let policy = Policy::new(Decision::Ask).with_prompt(|r| r.affordance == "motion");
+        assert_eq!(policy.check(&request).unwrap(), Decision::Ask);
+    }
+}
@@ -0,0 +1,366 @@
+//! Aggregate risk assessment.
+//!
+//! A [`RiskReport`] summarizes, for each hazard [`Category`], the risk
+//! scores of all the affordances of a Thing, while a [`HomeRiskReport`]
+//! does the same for a whole set of Things, such as the ones found by
+//! [`Discovery`](crate::Discovery).
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::ontology::{Category, Hazard};
+use crate::thing::{Hazard as ThingHazard, RiskScore, Thing};
+
+/// Risk associated to a hazard category.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CategoryRisk {
+    /// Hazard category.
+    pub category: Category,
+    /// Number of hazard annotations belonging to the category.
+    pub hazards: usize,
+    /// Number of hazard annotations without a risk score.
+    pub unscored: usize,
+    /// Highest risk score among the hazards.
+    pub max: Option<RiskScore>,
+    /// Sum of the risk scores of the hazards.
+    pub total: u32,
+}
+
+impl CategoryRisk {
+    fn new(category: Category) -> Self {
+        Self {
+            category,
+            hazards: 0,
+            unscored: 0,
+            max: None,
+            total: 0,
+        }
+    }
+
+    fn add(&mut self, score: Option<RiskScore>) {
+        self.hazards += 1;
+        match score {
+            Some(score) => {
+                self.max = self.max.max(Some(score));
+                self.total += u32::from(score.value());
+            }
+            None => self.unscored += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.hazards += other.hazards;
+        self.unscored += other.unscored;
+        self.max = self.max.max(other.max);
+        self.total += other.total;
+    }
+}
+
+fn write_rows(f: &mut fmt::Formatter, prefix: &str, categories: &[CategoryRisk]) -> fmt::Result {
+    for risk in categories {
+        let max = risk.max.map_or_else(|| "-".to_owned(), |v| v.to_string());
+        writeln!(
+            f,
+            "{}{:<12}{:>8}{:>10}{:>6}{:>7}",
+            prefix,
+            risk.category.to_string(),
+            risk.hazards,
+            risk.unscored,
+            max,
+            risk.total
+        )?;
+    }
+    Ok(())
+}
+
+/// Risk profile of a Thing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RiskReport {
+    /// Identifier of the Thing.
+    pub thing: String,
+    /// Title of the Thing.
+    pub title: String,
+    /// Risk for each category, including the ones without hazards.
+    pub categories: Vec<CategoryRisk>,
+    /// Hazards not defined by the ontology.
+    pub unknown: Vec<String>,
+}
+
+impl RiskReport {
+    /// Computes the risk profile of a Thing from the hazards of its
+    /// properties, actions and events.
+    pub fn for_thing(thing: &Thing) -> Self {
+        Self::from_hazards(&thing.id, &thing.title, thing.all_hazards())
+    }
+
+    fn from_hazards<'a>(
+        id: &str,
+        title: &str,
+        hazards: impl Iterator<Item = &'a ThingHazard>,
+    ) -> Self {
+        let mut categories: Vec<_> = Category::iter().map(CategoryRisk::new).collect();
+        let mut unknown = Vec::new();
+
+        for thing_hazard in hazards {
+            let category = Hazard::has_hazard(thing_hazard).and_then(|hazard| {
+                categories
+                    .iter_mut()
+                    .find(|risk| risk.category.all_hazards().contains(&hazard))
+            });
+            match category {
+                Some(risk) => risk.add(thing_hazard.risk_score()),
+                None => unknown.push(thing_hazard.name.clone()),
+            }
+        }
+
+        Self {
+            thing: id.to_owned(),
+            title: title.to_owned(),
+            categories,
+            unknown,
+        }
+    }
+
+    /// Returns the risk of a category.
+    pub fn category(&self, category: Category) -> Option<&CategoryRisk> {
+        self.categories
+            .iter()
+            .find(|risk| risk.category == category)
+    }
+
+    /// Returns the highest risk score among all the categories.
+    pub fn max(&self) -> Option<RiskScore> {
+        self.categories.iter().filter_map(|risk| risk.max).max()
+    }
+
+    /// Returns the sum of the risk scores of all the categories.
+    pub fn total(&self) -> u32 {
+        self.categories.iter().map(|risk| risk.total).sum()
+    }
+}
+
+impl fmt::Display for RiskReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} ({})", self.title, self.thing)?;
+        writeln!(
+            f,
+            "{:<12}{:>8}{:>10}{:>6}{:>7}",
+            "Category", "Hazards", "Unscored", "Max", "Total"
+        )?;
+        write_rows(f, "", &self.categories)?;
+        if !self.unknown.is_empty() {
+            writeln!(f, "Unknown hazards: {}", self.unknown.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Risk profile of a whole home.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HomeRiskReport {
+    /// Risk profile of each Thing.
+    pub things: Vec<RiskReport>,
+    /// Risk for each category, aggregated over all the Things.
+    pub categories: Vec<CategoryRisk>,
+}
+
+impl HomeRiskReport {
+    /// Computes the risk profile of a set of Things.
+    pub fn new<'a>(things: impl IntoIterator<Item = &'a Thing>) -> Self {
+        let things: Vec<_> = things.into_iter().map(RiskReport::for_thing).collect();
+        let mut categories: Vec<_> = Category::iter().map(CategoryRisk::new).collect();
+
+        for report in &things {
+            for (total, risk) in categories.iter_mut().zip(&report.categories) {
+                total.merge(risk);
+            }
+        }
+
+        Self { things, categories }
+    }
+
+    /// Returns the aggregated risk of a category.
+    pub fn category(&self, category: Category) -> Option<&CategoryRisk> {
+        self.categories
+            .iter()
+            .find(|risk| risk.category == category)
+    }
+
+    /// Returns the Things sorted from the riskiest to the safest one,
+    /// according to their highest risk score.
+    pub fn riskiest_things(&self) -> Vec<&RiskReport> {
+        let mut things: Vec<_> = self.things.iter().collect();
+        things.sort_by(|a, b| b.max().cmp(&a.max()).then(b.total().cmp(&a.total())));
+        things
+    }
+}
+
+impl fmt::Display for HomeRiskReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .things
+            .iter()
+            .map(|report| report.title.len())
+            .chain(Some("Home".len()))
+            .max()
+            .unwrap_or_default()
+            + 2;
+
+        writeln!(
+            f,
+            "{:<width$}{:<12}{:>8}{:>10}{:>6}{:>7}",
+            "Thing",
+            "Category",
+            "Hazards",
+            "Unscored",
+            "Max",
+            "Total",
+            width = width
+        )?;
+        for report in &self.things {
+            write_rows(
+                f,
+                &format!("{:<width$}", report.title, width = width),
+                &report.categories,
+            )?;
+        }
+        write_rows(
+            f,
+            &format!("{:<width$}", "Home", width = width),
+            &self.categories,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn oven() -> Thing {
+        serde_json::from_str(
+            r#"{
+                "@context": "https://www.w3.org/2019/wot/td/v1",
+                "id": "urn:dev:ops:my-oven-1234",
+                "title": "MyOven",
+                "securityDefinitions": {},
+                "properties": {
+                    "temperature": {
+                        "type": "integer",
+                        "hazards": [
+                            {
+                                "@id": "sho:ElectricEnergyConsumption",
+                                "name": "ElectricEnergyConsumption",
+                                "description": "",
+                                "riskScore": 3
+                            }
+                        ],
+                        "forms": [{"href": "https://myoven.example.com/temperature"}]
+                    }
+                },
+                "actions": {
+                    "bake": {
+                        "hazards": [
+                            {
+                                "@id": "sho:FireHazard",
+                                "name": "FireHazard",
+                                "description": "",
+                                "riskScore": 7
+                            },
+                            {
+                                "@id": "sho:ElectricEnergyConsumption",
+                                "name": "ElectricEnergyConsumption",
+                                "description": "",
+                                "riskScore": 5
+                            },
+                            {
+                                "@id": "ex:Unknown",
+                                "name": "Unknown",
+                                "description": ""
+                            }
+                        ],
+                        "forms": [{"href": "https://myoven.example.com/bake"}]
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn camera() -> Thing {
+        serde_json::from_str(
+            r#"{
+                "@context": "https://www.w3.org/2019/wot/td/v1",
+                "id": "urn:dev:ops:my-camera-1234",
+                "title": "MyCamera",
+                "securityDefinitions": {},
+                "events": {
+                    "motion": {
+                        "hazards": {
+                            "@id": "sho:TakePictures",
+                            "name": "TakePictures",
+                            "description": ""
+                        },
+                        "forms": [{"href": "https://mycamera.example.com/motion"}]
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn thing_report() {
+        let report = RiskReport::for_thing(&oven());
+
+        let financial = report.category(Category::Financial).unwrap();
+        assert_eq!(financial.hazards, 2);
+        assert_eq!(financial.max, RiskScore::new(5));
+        assert_eq!(financial.total, 8);
+        assert_eq!(report.category(Category::Privacy).unwrap().hazards, 0);
+        assert_eq!(report.max(), RiskScore::new(7));
+        assert_eq!(report.total(), 15);
+        assert_eq!(report.unknown, ["Unknown"]);
+
+        assert_eq!(
+            report.to_string(),
+            "\
+MyOven (urn:dev:ops:my-oven-1234)
+Category     Hazards  Unscored   Max  Total
+Financial          2         0     5      8
+Privacy            0         0     -      0
+Safety             1         0     7      7
+Unknown hazards: Unknown
+"
+        );
+        assert!(serde_json::to_value(&report).is_ok());
+    }
+
+    #[test]
+    fn home_report() {
+        let things = [camera(), oven()];
+        let report = HomeRiskReport::new(&things);
+
+        let privacy = report.category(Category::Privacy).unwrap();
+        assert_eq!(privacy.hazards, 1);
+        assert_eq!(privacy.unscored, 1);
+        assert_eq!(privacy.max, None);
+        assert_eq!(report.category(Category::Safety).unwrap().total, 7);
+        assert_eq!(report.riskiest_things()[0].title, "MyOven");
+        assert_eq!(
+            report.to_string(),
+            "\
+Thing     Category     Hazards  Unscored   Max  Total
+MyCamera  Financial          0         0     -      0
+MyCamera  Privacy            1         1     -      0
+MyCamera  Safety             0         0     -      0
+MyOven    Financial          2         0     5      8
+MyOven    Privacy            0         0     -      0
+MyOven    Safety             1         0     7      7
+Home      Financial          2         0     5      8
+Home      Privacy            1         1     -      0
+Home      Safety             1         0     7      7
+"
+        );
+    }
+}
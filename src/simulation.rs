@@ -0,0 +1,333 @@
+//! Dry run of planned interactions.
+//!
+//! A [`Simulator`] computes the hazards a sequence of interactions on
+//! discovered Things would trigger, and flags the dangerous combinations
+//! among them, without contacting any device.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::ontology::{Category, Hazard};
+use crate::policy::Interaction;
+use crate::thing::{Hazard as ThingHazard, Thing};
+
+/// Interaction planned on a Thing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedInteraction {
+    /// Identifier of the Thing.
+    pub thing: String,
+    /// Kind of interaction.
+    pub interaction: Interaction,
+    /// Name of the affordance.
+    pub affordance: String,
+}
+
+impl PlannedInteraction {
+    /// Creates a new `PlannedInteraction`.
+    pub fn new(thing: &str, interaction: Interaction, affordance: &str) -> Self {
+        Self {
+            thing: thing.to_owned(),
+            interaction,
+            affordance: affordance.to_owned(),
+        }
+    }
+}
+
+/// Hazards which are dangerous when triggered together.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DangerousCombination {
+    /// Hazards which must all be triggered, by any of the steps.
+    pub hazards: BTreeSet<Hazard>,
+    /// Why the combination is dangerous.
+    pub reason: String,
+}
+
+impl DangerousCombination {
+    /// Creates a new `DangerousCombination`.
+    pub fn new(hazards: impl IntoIterator<Item = Hazard>, reason: &str) -> Self {
+        Self {
+            hazards: hazards.into_iter().collect(),
+            reason: reason.to_owned(),
+        }
+    }
+
+    /// Returns the combinations checked by default.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new(
+                [Hazard::GasConsumption, Hazard::FireHazard],
+                "Gas released close to a fire source may ignite",
+            ),
+            Self::new(
+                [Hazard::WaterConsumption, Hazard::PowerSurge],
+                "Water close to high voltages may cause electrocution",
+            ),
+        ]
+    }
+}
+
+/// Outcome of a single planned interaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SimulatedStep {
+    /// Simulated interaction.
+    pub planned: PlannedInteraction,
+    /// Hazards triggered by the interaction.
+    pub hazards: BTreeSet<Hazard>,
+    /// Hazards not defined by the ontology.
+    pub unknown: Vec<String>,
+}
+
+/// Dangerous combination triggered by a plan.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TriggeredCombination {
+    /// Combination whose hazards have all been triggered.
+    pub combination: DangerousCombination,
+    /// Indices of the steps contributing to the combination.
+    pub steps: Vec<usize>,
+}
+
+/// Outcome of a dry run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SimulationReport {
+    /// Outcome of each planned interaction, in order.
+    pub steps: Vec<SimulatedStep>,
+    /// Union of the hazards triggered by all the steps.
+    pub hazards: BTreeSet<Hazard>,
+    /// Categories of the triggered hazards.
+    pub categories: BTreeSet<Category>,
+    /// Dangerous combinations triggered by the plan.
+    pub dangerous: Vec<TriggeredCombination>,
+}
+
+impl SimulationReport {
+    /// Returns whether the plan triggers any dangerous combination.
+    pub fn is_dangerous(&self) -> bool {
+        !self.dangerous.is_empty()
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            let hazards: Vec<_> = step
+                .hazards
+                .iter()
+                .map(Hazard::to_string)
+                .chain(step.unknown.iter().cloned())
+                .collect();
+            writeln!(
+                f,
+                "{}. {} `{}` of `{}`: {}",
+                i + 1,
+                step.planned.interaction,
+                step.planned.affordance,
+                step.planned.thing,
+                if hazards.is_empty() {
+                    "no hazards".to_owned()
+                } else {
+                    hazards.join(", ")
+                }
+            )?;
+        }
+        for triggered in &self.dangerous {
+            let hazards: Vec<_> = triggered
+                .combination
+                .hazards
+                .iter()
+                .map(Hazard::to_string)
+                .collect();
+            let steps: Vec<_> = triggered
+                .steps
+                .iter()
+                .map(|i| (i + 1).to_string())
+                .collect();
+            writeln!(
+                f,
+                "DANGER {} (steps {}): {}",
+                hazards.join(" + "),
+                steps.join(", "),
+                triggered.combination.reason
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Dry run simulator over a set of Things.
+#[derive(Clone, Debug)]
+pub struct Simulator<'a> {
+    things: HashMap<&'a str, &'a Thing>,
+    combinations: Vec<DangerousCombination>,
+}
+
+impl<'a> Simulator<'a> {
+    /// Creates a simulator checking the default dangerous combinations.
+    pub fn new(things: impl IntoIterator<Item = &'a Thing>) -> Self {
+        Self {
+            things: things
+                .into_iter()
+                .map(|thing| (thing.id.as_str(), thing))
+                .collect(),
+            combinations: DangerousCombination::defaults(),
+        }
+    }
+
+    /// Adds a dangerous combination to check.
+    pub fn with_combination(mut self, combination: DangerousCombination) -> Self {
+        self.combinations.push(combination);
+        self
+    }
+
+    fn hazards(&self, planned: &PlannedInteraction) -> Result<&'a [ThingHazard]> {
+        let thing = self
+            .things
+            .get(planned.thing.as_str())
+            .ok_or_else(|| anyhow!("Unknown thing `{}`", planned.thing))?;
+        let name = planned.affordance.as_str();
+
+        let hazards = match planned.interaction {
+            Interaction::WriteProperty => thing.properties.get(name).map(|v| v.hazards()),
+            Interaction::InvokeAction => thing.actions.get(name).map(|v| v.hazards()),
+            Interaction::SubscribeEvent => thing.events.get(name).map(|v| v.hazards()),
+        };
+
+        hazards.ok_or_else(|| {
+            anyhow!(
+                "Unknown affordance `{}` for {} on `{}`",
+                name,
+                planned.interaction,
+                planned.thing
+            )
+        })
+    }
+
+    /// Simulates a sequence of interactions.
+    ///
+    /// It fails if an interaction refers to an unknown Thing or affordance.
+    pub fn simulate(&self, plan: &[PlannedInteraction]) -> Result<SimulationReport> {
+        let mut steps = Vec::with_capacity(plan.len());
+
+        for planned in plan {
+            let mut hazards = BTreeSet::new();
+            let mut unknown = Vec::new();
+            for thing_hazard in self.hazards(planned)? {
+                match Hazard::has_hazard(thing_hazard) {
+                    Some(hazard) => {
+                        hazards.insert(hazard);
+                    }
+                    None => unknown.push(thing_hazard.name.clone()),
+                }
+            }
+            steps.push(SimulatedStep {
+                planned: planned.clone(),
+                hazards,
+                unknown,
+            });
+        }
+
+        let hazards: BTreeSet<_> = steps
+            .iter()
+            .flat_map(|step| step.hazards.iter().copied())
+            .collect();
+        let categories = hazards.iter().map(Hazard::has_category).collect();
+
+        let dangerous = self
+            .combinations
+            .iter()
+            .filter(|combination| combination.hazards.is_subset(&hazards))
+            .map(|combination| TriggeredCombination {
+                combination: combination.clone(),
+                steps: steps
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, step)| !step.hazards.is_disjoint(&combination.hazards))
+                    .map(|(i, _)| i)
+                    .collect(),
+            })
+            .collect();
+
+        Ok(SimulationReport {
+            steps,
+            hazards,
+            categories,
+            dangerous,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn thing(id: &str, action: &str, hazards: &[&str]) -> Thing {
+        let hazards: Vec<_> = hazards
+            .iter()
+            .map(|name| {
+                serde_json::json!({
+                    "@id": format!("sho:{}", name),
+                    "name": name,
+                    "description": "",
+                })
+            })
+            .collect();
+
+        serde_json::from_value(serde_json::json!({
+            "@context": "https://www.w3.org/2019/wot/td/v1",
+            "id": id,
+            "title": id,
+            "securityDefinitions": {},
+            "actions": {
+                action: {
+                    "hazards": hazards,
+                    "forms": [{"href": "http://192.0.2.1/action"}]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn dangerous_plan() {
+        let things = [
+            thing("urn:stove", "ignite", &["FireHazard", "GasConsumption"]),
+            thing("urn:sprinkler", "water", &["WaterConsumption"]),
+            thing("urn:lamp", "toggle", &[]),
+        ];
+        let simulator = Simulator::new(&things);
+
+        let plan = [
+            PlannedInteraction::new("urn:lamp", Interaction::InvokeAction, "toggle"),
+            PlannedInteraction::new("urn:sprinkler", Interaction::InvokeAction, "water"),
+        ];
+        let report = simulator.simulate(&plan).unwrap();
+        assert_eq!(report.hazards.len(), 1);
+        assert!(report.categories.contains(&Category::Financial));
+        assert!(!report.is_dangerous());
+
+        let plan = [
+            PlannedInteraction::new("urn:sprinkler", Interaction::InvokeAction, "water"),
+            PlannedInteraction::new("urn:stove", Interaction::InvokeAction, "ignite"),
+        ];
+        let report = simulator.simulate(&plan).unwrap();
+        assert_eq!(report.dangerous.len(), 1);
+        assert_eq!(report.dangerous[0].steps, [1]);
+        assert!(report.to_string().contains("DANGER"));
+
+        let simulator = simulator.with_combination(DangerousCombination::new(
+            [Hazard::WaterConsumption, Hazard::FireHazard],
+            "Test",
+        ));
+        let report = simulator.simulate(&plan).unwrap();
+        assert_eq!(report.dangerous[1].steps, [0, 1]);
+
+        let plan = [PlannedInteraction::new(
+            "urn:stove",
+            Interaction::WriteProperty,
+            "ignite",
+        )];
+        assert!(simulator.simulate(&plan).is_err());
+    }
+}
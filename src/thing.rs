@@ -31,11 +31,119 @@ impl<'de> Deserialize<'de> for SchemaType {
                 ))
             }
         } else {
-            Err("Error parsing DataSchema").map_err(serde::de::Error::custom)
+            Err(serde::de::Error::custom("Error parsing DataSchema"))
         }
     }
 }
 
+/// SIFIS risk score
+///
+/// A risk score can *only* assume values in the range [0, 10],
+/// as restricted by the `sho:level` datatype of the ontology.
+/// Values outside of the defined range are rejected while parsing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct RiskScore(u8);
+
+impl RiskScore {
+    /// Lowest admissible risk score.
+    pub const MIN: u8 = crate::ontology::RISK_SCORE_MIN;
+    /// Highest admissible risk score.
+    pub const MAX: u8 = crate::ontology::RISK_SCORE_MAX;
+
+    /// Creates a new `RiskScore`.
+    ///
+    /// It returns `None` if the value is outside of the [0, 10] range.
+    pub fn new(value: usize) -> Option<Self> {
+        (Self::MIN as usize..=Self::MAX as usize)
+            .contains(&value)
+            .then_some(Self(value as u8))
+    }
+
+    /// Returns the risk score as an integer.
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Adds two risk scores, clamping the result to the highest score.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0).min(Self::MAX))
+    }
+
+    /// Subtracts two risk scores, clamping the result to the lowest score.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Returns the sum of the given risk scores.
+    ///
+    /// The sum is not clamped, so it can exceed the highest score.
+    pub fn total<I: IntoIterator<Item = Self>>(scores: I) -> u32 {
+        scores.into_iter().map(|v| u32::from(v.0)).sum()
+    }
+
+    /// Returns the arithmetic mean of the given risk scores.
+    ///
+    /// It returns `None` if there are no scores.
+    pub fn mean<I: IntoIterator<Item = Self>>(scores: I) -> Option<f64> {
+        let (count, sum) = scores.into_iter().fold((0u32, 0u32), |(count, sum), v| {
+            (count + 1, sum + u32::from(v.0))
+        });
+        (count > 0).then(|| f64::from(sum) / f64::from(count))
+    }
+}
+
+impl std::fmt::Display for RiskScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Error returned when a value is not a valid `RiskScore`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidRiskScore(pub i64);
+
+impl std::fmt::Display for InvalidRiskScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid risk score {}, expected a value in the range [{}, {}]",
+            self.0,
+            RiskScore::MIN,
+            RiskScore::MAX
+        )
+    }
+}
+
+impl std::error::Error for InvalidRiskScore {}
+
+impl TryFrom<i64> for RiskScore {
+    type Error = InvalidRiskScore;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        usize::try_from(value)
+            .ok()
+            .and_then(Self::new)
+            .ok_or(InvalidRiskScore(value))
+    }
+}
+
+impl From<RiskScore> for u8 {
+    fn from(score: RiskScore) -> Self {
+        score.0
+    }
+}
+
+impl<'de> Deserialize<'de> for RiskScore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = i64::deserialize(deserializer)?;
+        RiskScore::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
 /// SIFIS Hazard
 ///
 /// Describes a possible hazard.
@@ -49,12 +157,83 @@ pub struct Hazard {
     pub name: String,
     pub description: String,
     #[serde(rename = "riskScore")]
-    risk_score: Option<usize>,
+    risk_score: Option<RiskScore>,
 }
 
 impl Hazard {
     pub fn has_valid_risk_score(&self) -> bool {
-        self.risk_score.map_or(false, |v| (0..11).contains(&v))
+        self.risk_score.is_some()
+    }
+
+    /// Returns the risk score associated to the hazard, if any.
+    pub fn risk_score(&self) -> Option<RiskScore> {
+        self.risk_score
+    }
+}
+
+/// Invalid risk score skipped while parsing a Thing Description
+/// in lenient mode.
+#[derive(Clone, Debug)]
+pub struct RiskScoreDiagnostic {
+    /// JSON Pointer to the invalid `riskScore` entry.
+    pub pointer: String,
+    /// The rejected value.
+    pub value: Value,
+}
+
+impl std::fmt::Display for RiskScoreDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: invalid risk score {}", self.pointer, self.value)
+    }
+}
+
+// Escapes a key to be a JSON Pointer reference token.
+fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+// Removes the invalid risk score of a hazard entry, recording where it
+// was found.
+fn strip_invalid_risk_score(
+    hazard: &mut Value,
+    pointer: String,
+    diagnostics: &mut Vec<RiskScoreDiagnostic>,
+) {
+    let Some(hazard) = hazard.as_object_mut() else {
+        return;
+    };
+    let invalid = hazard
+        .get("riskScore")
+        .is_some_and(|v| !v.is_null() && RiskScore::deserialize(v).is_err());
+    if invalid {
+        let value = hazard.remove("riskScore").unwrap_or_default();
+        diagnostics.push(RiskScoreDiagnostic {
+            pointer: format!("{}/riskScore", pointer),
+            value,
+        });
+    }
+}
+
+// Removes the invalid risk scores from the hazards of the interaction
+// affordances of a Thing Description, recording where they were found.
+// Other `riskScore` keys, such as the name of a property, are kept.
+fn strip_invalid_risk_scores(value: &mut Value, diagnostics: &mut Vec<RiskScoreDiagnostic>) {
+    for kind in ["properties", "actions", "events"] {
+        let Some(affordances) = value.get_mut(kind).and_then(Value::as_object_mut) else {
+            continue;
+        };
+        for (name, affordance) in affordances {
+            let pointer = format!("/{}/{}/hazards", kind, pointer_token(name));
+            match affordance.get_mut("hazards") {
+                Some(Value::Array(hazards)) => {
+                    for (i, hazard) in hazards.iter_mut().enumerate() {
+                        strip_invalid_risk_score(hazard, format!("{}/{}", pointer, i), diagnostics);
+                    }
+                }
+                Some(hazard) => strip_invalid_risk_score(hazard, pointer, diagnostics),
+                None => {}
+            }
+        }
     }
 }
 
@@ -77,7 +256,6 @@ pub struct DataSchema {
     #[serde(rename = "WriteOnly")]
     pub write_only: Option<String>,
     pub format: Option<String>,
-    pub hazards: Option<Hazard>,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -109,6 +287,13 @@ pub struct Affordance {
     pub forms: Vec<Form>,
     #[serde(rename = "uriVariables")]
     pub uri_variables: Option<DataSchemaMap>,
+    /// Hazards of the interaction.
+    ///
+    /// The schema of a property is flattened along with its affordance, so
+    /// the `hazards` key is read only here.
+    #[serde(default = "Vec::new")]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    pub hazards: Vec<Hazard>,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -125,6 +310,11 @@ impl Property {
         self.affordance.attype.contains(&has_attype.to_owned())
     }
 
+    /// Returns the hazards associated to the property.
+    pub fn hazards(&self) -> &[Hazard] {
+        &self.affordance.hazards
+    }
+
     pub fn set<T: Serialize>(&self, _val: T) -> anyhow::Result<()> {
         todo!("Mockup");
     }
@@ -146,6 +336,13 @@ pub struct Action {
     pub idempotent: bool,
 }
 
+impl Action {
+    /// Returns the hazards associated to the action.
+    pub fn hazards(&self) -> &[Hazard] {
+        &self.affordance.hazards
+    }
+}
+
 #[derive(Clone, Deserialize, Debug)]
 pub struct Event {
     #[serde(flatten)]
@@ -155,6 +352,13 @@ pub struct Event {
     pub cancellation: Option<DataSchema>,
 }
 
+impl Event {
+    /// Returns the hazards associated to the event.
+    pub fn hazards(&self) -> &[Hazard] {
+        &self.affordance.hazards
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct SecurityScheme {
     // FIXME Update with new security scheme
@@ -221,6 +425,37 @@ impl Thing {
     pub fn has_attype(&self, has_attype: &str) -> bool {
         self.attype.contains(&has_attype.to_owned())
     }
+
+    /// Returns the hazards of all the properties, actions and events
+    /// exposed by the Thing.
+    pub fn all_hazards(&self) -> impl Iterator<Item = &Hazard> {
+        self.properties
+            .values()
+            .flat_map(Property::hazards)
+            .chain(self.actions.values().flat_map(Action::hazards))
+            .chain(self.events.values().flat_map(Event::hazards))
+    }
+
+    /// Parses a Thing Description without failing on invalid risk scores.
+    ///
+    /// Invalid risk scores are dropped from the hazards and returned as
+    /// diagnostics, any other error is still reported.
+    pub fn from_value_lenient(
+        mut value: Value,
+    ) -> serde_json::Result<(Self, Vec<RiskScoreDiagnostic>)> {
+        let mut diagnostics = Vec::new();
+        strip_invalid_risk_scores(&mut value, &mut diagnostics);
+        let thing = serde_json::from_value(value)?;
+
+        Ok((thing, diagnostics))
+    }
+
+    /// Parses a Thing Description from a string in lenient mode.
+    ///
+    /// See [`Thing::from_value_lenient`].
+    pub fn from_str_lenient(s: &str) -> serde_json::Result<(Self, Vec<RiskScoreDiagnostic>)> {
+        Self::from_value_lenient(serde_json::from_str(s)?)
+    }
 }
 
 #[cfg(test)]
@@ -399,4 +634,103 @@ mod test {
 
         println!("{:?}", td);
     }
+
+    #[test]
+    fn sifis_hazards() {
+        let ex = r#"
+        {
+            "@context": [
+                "https://www.w3.org/2019/wot/td/v1",
+                { "sho": "https://purl.org/sifis/hazards#" }
+            ],
+            "id": "urn:dev:ops:my-oven-1234",
+            "title": "MyOven",
+            "securityDefinitions": {
+                "nosec_sc": {"scheme": "nosec"}
+            },
+            "security": "nosec_sc",
+            "properties": {
+                "temperature": {
+                    "type": "integer",
+                    "hazards": {
+                        "@id": "sho:ElectricEnergyConsumption",
+                        "name": "ElectricEnergyConsumption",
+                        "description": "The execution enables a device that consumes electricity",
+                        "riskScore": 3
+                    },
+                    "forms": [{"href": "https://myoven.example.com/temperature"}]
+                },
+                "riskScore": {
+                    "type": "integer",
+                    "forms": [{"href": "https://myoven.example.com/riskScore"}]
+                }
+            },
+            "actions": {
+                "bake": {
+                    "hazards": [
+                        {
+                            "@id": "sho:FireHazard",
+                            "name": "FireHazard",
+                            "description": "The execution may cause fire",
+                            "riskScore": 7
+                        },
+                        {
+                            "@id": "sho:ElectricEnergyConsumption",
+                            "name": "ElectricEnergyConsumption",
+                            "description": "The execution enables a device that consumes electricity",
+                            "riskScore": 11
+                        }
+                    ],
+                    "forms": [{"href": "https://myoven.example.com/bake"}]
+                }
+            },
+            "events": {
+                "overheating": {
+                    "data": {"type": "string"},
+                    "forms": [{"href": "https://myoven.example.com/oh"}]
+                }
+            }
+        }"#;
+
+        assert!(serde_json::from_str::<Thing>(ex).is_err());
+
+        let (td, diagnostics) = Thing::from_str_lenient(ex).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pointer, "/actions/bake/hazards/1/riskScore");
+        assert_eq!(diagnostics[0].value, 11);
+
+        let temperature = td.properties["temperature"].hazards();
+        assert_eq!(temperature.len(), 1);
+        assert_eq!(temperature[0].risk_score().map(RiskScore::value), Some(3));
+
+        let bake = td.actions["bake"].hazards();
+        assert_eq!(bake.len(), 2);
+        assert_eq!(bake[0].name, "FireHazard");
+        assert_eq!(bake[0].risk_score().map(RiskScore::value), Some(7));
+        assert!(!bake[1].has_valid_risk_score());
+
+        assert!(td.properties.contains_key("riskScore"));
+        assert!(td.events["overheating"].hazards().is_empty());
+        assert_eq!(td.all_hazards().count(), 3);
+    }
+
+    #[test]
+    fn risk_score() {
+        assert_eq!(RiskScore::new(10).map(RiskScore::value), Some(10));
+        assert_eq!(RiskScore::new(11), None);
+        assert_eq!(RiskScore::try_from(-1), Err(InvalidRiskScore(-1)));
+
+        assert!(serde_json::from_str::<RiskScore>("7").is_ok());
+        assert!(serde_json::from_str::<RiskScore>("11").is_err());
+        assert!(serde_json::from_str::<RiskScore>("-3").is_err());
+        assert!(serde_json::from_str::<RiskScore>("\"\"").is_err());
+
+        let scores = [3, 7, 8].map(|v| RiskScore::new(v).unwrap());
+        assert_eq!(scores.iter().max().map(|v| v.value()), Some(8));
+        assert_eq!(RiskScore::total(scores), 18);
+        assert_eq!(RiskScore::mean(scores), Some(6.0));
+        assert_eq!(RiskScore::mean([]), None);
+        assert_eq!(scores[1].saturating_add(scores[2]).value(), 10);
+        assert_eq!(scores[0].saturating_sub(scores[1]).value(), 0);
+    }
 }